@@ -0,0 +1,36 @@
+//! Compares pushing a batch of elements one at a time with
+//! `copy_from_slice`, which copies them in at most two runs.
+//!
+//! Run with `cargo bench --bench copy`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use circular_buff::RingBuff;
+
+const CAP: usize = 4096;
+const BATCH: usize = 3000;
+const ROUNDS: usize = 20_000;
+
+fn time_batches(copy: impl Fn(&mut RingBuff<u64, CAP>, &[u64])) -> Duration {
+    let batch: Vec<u64> = (0..BATCH as u64).collect();
+    let mut buffer = RingBuff::new();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        copy(black_box(&mut buffer), black_box(&batch));
+    }
+    start.elapsed()
+}
+
+fn main() {
+    println!(
+        "push_back:       {:?}",
+        time_batches(|buffer, batch| {
+            for &element in batch {
+                buffer.push_back(element);
+            }
+        })
+    );
+    println!("copy_from_slice: {:?}", time_batches(|buffer, batch| buffer.copy_from_slice(batch)));
+}
@@ -0,0 +1,43 @@
+//! Compares `rotate_left` on a contiguous buffer, rotated in place,
+//! with a wrapped one, where elements are moved one at a time.
+//!
+//! Run with `cargo bench --bench rotate`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use circular_buff::RingBuff;
+
+const CAP: usize = 4096;
+const ROUNDS: usize = 2_000;
+
+/// Builds a buffer holding `CAP - 1` elements, starting at `offset`
+fn buffer_at(offset: usize) -> RingBuff<u64, CAP> {
+    let mut buffer = RingBuff::new();
+    for i in 0..offset {
+        buffer.push_back(i as u64);
+    }
+    for _ in 0..offset {
+        buffer.pop();
+    }
+    for i in 0..CAP - 1 {
+        buffer.push_back(i as u64);
+    }
+    buffer
+}
+
+fn time_rotations(buffer: &mut RingBuff<u64, CAP>) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.rotate_left(black_box(CAP / 3));
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let mut contiguous = buffer_at(0);
+    let mut wrapped = buffer_at(CAP / 2);
+
+    println!("contiguous: {:?}", time_rotations(&mut contiguous));
+    println!("wrapped:    {:?}", time_rotations(&mut wrapped));
+}
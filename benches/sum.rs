@@ -0,0 +1,39 @@
+//! Compares summing a wrapped buffer through `iter`
+//! with `sum_copied`, which reads the two runs of the data array.
+//!
+//! Run with `cargo bench --bench sum`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use circular_buff::RingBuff;
+
+const CAP: usize = 4096;
+const ROUNDS: usize = 20_000;
+
+/// Builds a full buffer whose oldest element sits in the middle of the data array
+fn wrapped_buffer() -> RingBuff<u64, CAP> {
+    let mut buffer = RingBuff::new();
+    for i in 0..CAP + CAP / 2 {
+        buffer.push_back(i as u64);
+    }
+    buffer
+}
+
+fn time_sums(sum: impl Fn(&RingBuff<u64, CAP>) -> u64, buffer: &RingBuff<u64, CAP>) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(sum(black_box(buffer)));
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let buffer = wrapped_buffer();
+    assert_eq!(buffer.iter().sum::<u64>(), buffer.sum_copied());
+
+    println!("iter().sum(): {:?}", time_sums(|b| b.iter().sum(), &buffer));
+    println!("sum_copied(): {:?}", time_sums(|b| b.sum_copied(), &buffer));
+    println!("iter().max(): {:?}", time_sums(|b| *b.iter().max().unwrap(), &buffer));
+    println!("max_copied(): {:?}", time_sums(|b| b.max_copied().unwrap(), &buffer));
+}
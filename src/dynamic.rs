@@ -0,0 +1,233 @@
+//! Heap backed ring buffer, enabled by the `std` feature
+//!
+//! `DynRingBuff` has the same FIFO behavior as `RingBuff`, with
+//! a capacity chosen at runtime instead of a const generic.
+//! The elements are stored as `Option`s in a `Vec` rather than in
+//! uninitialized slots, so only the index arithmetic is shared with
+//! `RingBuff`, through the `wrap` module.
+
+use core::fmt::{self, Debug};
+
+use crate::wrap;
+
+/// Ring buffer implementation with a runtime capacity
+pub struct DynRingBuff<T> {
+    /// The data is stored in a vector holding one slot per element
+    data: Vec<Option<T>>,
+    /// The queue head
+    reader: usize,
+    /// The queue tail
+    writer: usize,
+    /// Number of elements in the queue
+    size: usize,
+}
+
+impl<T> DynRingBuff<T> {
+    /// Return a new Ring Buffer holding up to `capacity` elements.
+    /// With a capacity of 0 every pushed element is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of elements
+    ///
+    /// # Examples
+    /// `let buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(4);`
+    ///
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: (0..capacity).map(|_| None).collect(),
+            reader: 0,
+            writer: 0,
+            size: 0,
+        }
+    }
+
+    /// Pushes one element to the back of the queue,
+    /// overwriting the oldest element when the buffer is full.
+    ///
+    /// # Arguments
+    /// * `element` - The element to add to the queue
+    pub fn push_back(&mut self, element: T) {
+        if self.capacity() == 0 {
+            return;
+        }
+
+        if self.is_full() {
+            self.reader = self.next_index(self.reader);
+        } else {
+            self.size += 1;
+        }
+
+        self.data[self.writer] = Some(element);
+        self.writer = self.next_index(self.writer);
+    }
+
+    /// Remove one element from the front of the queue
+    /// and returns it.
+    ///
+    /// # Arguments
+    ///
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let element = self.data[self.reader].take();
+        self.reader = self.next_index(self.reader);
+        self.size -= 1;
+
+        element
+    }
+
+    /// Changes the capacity of the buffer, keeping the order of
+    /// the elements. When shrinking below the length, the oldest
+    /// elements are dropped and the `new_cap` newest ones kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_cap` - The new maximum number of elements
+    ///
+    pub fn resize(&mut self, new_cap: usize) {
+        while self.len() > new_cap {
+            self.pop();
+        }
+
+        let mut data: Vec<Option<T>> = Vec::with_capacity(new_cap);
+        let size = self.len();
+        while let Some(element) = self.pop() {
+            data.push(Some(element));
+        }
+        data.resize_with(new_cap, || None);
+
+        self.data = data;
+        self.reader = 0;
+        self.writer = if new_cap == 0 { 0 } else { size % new_cap };
+        self.size = size;
+    }
+
+    /// Returns the index of the next element in data.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The original index
+    ///
+    fn next_index(&self, index: usize) -> usize {
+        wrap::next(index, self.capacity())
+    }
+
+    /// Converts an index from the reader to its corresponding
+    /// index in the data vector
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The original index
+    ///
+    fn relative_to_absolute_index(&self, index: usize) -> Option<usize> {
+        if index >= self.len() {
+            None
+        } else {
+            Some(wrap::add(self.reader, index, self.capacity()))
+        }
+    }
+
+    /// Removes all elements in the buffer.
+    ///
+    /// # Arguments
+    ///
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+
+        self.reader = 0;
+        self.writer = 0;
+    }
+
+    /// Returns true if the buffer contains no elements.
+    ///
+    /// # Arguments
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns whether or not the buffer is full.
+    ///
+    /// # Arguments
+    ///
+    fn is_full(&self) -> bool {
+        self.size == self.capacity()
+    }
+
+    /// Returns the number of elements in the buffer.
+    ///
+    /// # Arguments
+    ///
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the maximum number of elements the
+    /// buffer can hold.
+    ///
+    /// # Arguments
+    ///
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a reference to an element or None
+    /// if the index is out of bounds.
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element to look up
+    ///
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let i = self.relative_to_absolute_index(index)?;
+        self.data[i].as_ref()
+    }
+
+    /// Returns a mutable reference to an element or None
+    /// if the index is out of bounds
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element to look up
+    ///
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let i = self.relative_to_absolute_index(index)?;
+        self.data[i].as_mut()
+    }
+
+    /// Returns an iterator on the buffer, from the
+    /// oldest element to the newest
+    ///
+    /// # Arguments
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len()).map(move |i| self.get(i).expect("Index is valid."))
+    }
+}
+
+impl<T: Debug> Debug for DynRingBuff<T> {
+    /// Lists the elements from the oldest to the newest.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for DynRingBuff<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            reader: self.reader,
+            writer: self.writer,
+            size: self.size,
+        }
+    }
+
+    /// Reuses the storage of the buffer with `Vec::clone_from`.
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.reader = source.reader;
+        self.writer = source.writer;
+        self.size = source.size;
+    }
+}
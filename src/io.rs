@@ -0,0 +1,51 @@
+//! `std::io` support, enabled by the `std` feature
+//!
+//! A `RingBuff<u8, CAP>` can be used as a byte sink with `Write`
+//! and as a byte source with `Read`.
+
+use std::io::{self, Read, Write};
+
+use crate::{OverflowPolicy, RingBuff};
+
+impl<const CAP: usize> Write for RingBuff<u8, CAP> {
+    /// Pushes the bytes to the back of the queue and returns how
+    /// many were accepted. With `OverflowPolicy::Overwrite` every
+    /// byte is accepted and the oldest ones are overwritten, with
+    /// `OverflowPolicy::Reject` writing stops once the buffer is full.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+
+        for &byte in buf {
+            if self.is_full() && self.policy == OverflowPolicy::Reject {
+                break;
+            }
+            self.push_back(byte);
+            written += 1;
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<const CAP: usize> Read for RingBuff<u8, CAP> {
+    /// Pops bytes from the front of the queue into `buf`, oldest
+    /// first, and returns how many were read. Returns `Ok(0)` once
+    /// the buffer is empty.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+
+        for slot in buf.iter_mut() {
+            if self.is_empty() {
+                break;
+            }
+            *slot = self.pop().expect("Buffer is not empty.");
+            read += 1;
+        }
+
+        Ok(read)
+    }
+}
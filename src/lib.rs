@@ -10,273 +10,2844 @@
 //! Also called Ring buffer or circular queue
 //! The implementation stores data on the stack, for blablalbal
 //! It should not be used to store too large data sets, since it could cause an overflow
+//!
+//! The crate is `no_std` when the default `std` feature is disabled,
+//! only the helpers returning or filling a `Vec`, the `std::io`
+//! implementations, the heap backed `DynRingBuff` and the `spsc`
+//! channel need it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::iter::Sum;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::Range;
+use core::ptr;
+
+#[cfg(feature = "std")]
+mod dynamic;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "std")]
+pub mod spsc;
+mod wrap;
 
-#[no_std]
-use core::fmt::{Debug};
-use core::mem;
+#[cfg(feature = "std")]
+pub use dynamic::DynRingBuff;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test;
 
+/// Behavior of `push_back` when the buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// The oldest element is overwritten by the new one
+    #[default]
+    Overwrite,
+    /// The new element is dropped, the buffer is left unchanged
+    Reject,
+}
+
+/// Position of a Ring Buffer saved by `checkpoint`
+/// and brought back by `restore`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The queue head
+    reader: usize,
+    /// The queue tail
+    writer: usize,
+    /// Number of elements in the queue
+    size: usize,
+    /// Number of overwrites when the checkpoint was taken
+    overwrites: u64,
+    /// Number of pushes when the checkpoint was taken
+    pushes: u64,
+    /// Sequence id of the oldest element
+    front_id: u64,
+}
+
+/// Error returned by `checked_push` when the buffer is full,
+/// holding the element that could not be pushed
+#[derive(Clone, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    /// The rejected element
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Returns the element that could not be pushed.
+    ///
+    /// # Arguments
+    ///
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+
+    /// Returns a reference to the element that could not be pushed.
+    ///
+    /// # Arguments
+    ///
+    pub const fn element(&self) -> &T {
+        &self.element
+    }
+}
+
+impl<T> Debug for CapacityError<T> {
+    /// Does not require `T: Debug`, the element is left out.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacityError").finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the buffer is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for CapacityError<T> {}
+
 /// Ring buffer implementation
-#[derive(Debug)]
 pub struct RingBuff<T, const CAP: usize> {
-    /// The data is stored in an array
-    data: [Option<T>; CAP],
+    /// The data is stored in an array, only the `size` slots
+    /// starting from `reader` are initialized
+    data: [MaybeUninit<T>; CAP],
     /// The queue head
     reader: usize,
     /// The queue tail
     writer: usize,
     /// Number of elements in the queue
     size: usize,
+    /// What to do when pushing into a full queue
+    policy: OverflowPolicy,
+    /// Number of elements overwritten by a push
+    overwrites: u64,
+    /// Number of elements added to the queue,
+    /// checked by `restore`
+    pushes: u64,
+    /// Sequence id of the oldest element, incremented
+    /// each time an element leaves the front of the queue
+    front_id: u64,
 }
 
 impl<T, const CAP: usize> RingBuff<T, CAP> {
-    /// Return a new Ring Buffer
+    /// Return a new Ring Buffer.
+    /// It can be used in const contexts, e.g. to initialize a `static`.
     ///
     /// # Arguments
     ///
     /// # Examples
     /// `let buffer: RingBuff<i32, 4> = RingBuff::new();`
     ///
-    pub fn new() -> Self
-        where
-            T: Copy, {
+    pub const fn new() -> Self {
+        // `OverflowPolicy::default()` is not callable in const contexts
+        Self::with_policy(OverflowPolicy::Overwrite)
+    }
+
+    /// Return a new Ring Buffer applying the given
+    /// policy when pushing into a full buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The overflow policy
+    ///
+    /// # Examples
+    /// `let buffer: RingBuff<i32, 4> = RingBuff::with_policy(OverflowPolicy::Reject);`
+    ///
+    pub const fn with_policy(policy: OverflowPolicy) -> Self {
         Self {
-            data: [None; CAP],
+            data: [const { MaybeUninit::uninit() }; CAP],
             reader: 0,
             writer: 0,
             size: 0,
+            policy,
+            overwrites: 0,
+            pushes: 0,
+            front_id: 0,
+        }
+    }
+
+    /// Rebuilds a Ring Buffer from its raw components,
+    /// checking that they are consistent.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data array
+    /// * `reader` - The index of the queue head in `data`
+    /// * `writer` - The index of the queue tail in `data`
+    /// * `size` - Number of elements in the queue
+    ///
+    /// # Errors
+    /// Returns an error if an index is out of range, if `size` exceeds
+    /// the capacity, or if the `Some` slots are not exactly the `size`
+    /// slots starting from `reader`.
+    ///
+    pub fn from_parts(data: [Option<T>; CAP], reader: usize, writer: usize, size: usize) -> Result<Self, &'static str> {
+        Self::check_positions(reader, writer, size)?;
+
+        for (i, slot) in data.iter().enumerate() {
+            let live = (i + CAP - reader) % CAP < size;
+            if live != slot.is_some() {
+                return Err("live slots do not match reader and size");
+            }
+        }
+
+        Ok(Self {
+            data: data.map(|slot| match slot {
+                Some(element) => MaybeUninit::new(element),
+                None => MaybeUninit::uninit(),
+            }),
+            reader,
+            writer,
+            size,
+            policy: OverflowPolicy::default(),
+            overwrites: 0,
+            pushes: 0,
+            front_id: 0,
+        })
+    }
+
+    /// Checks that the positions of the queue head and tail
+    /// are consistent with the number of elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The index of the queue head in the data array
+    /// * `writer` - The index of the queue tail in the data array
+    /// * `size` - Number of elements in the queue
+    ///
+    fn check_positions(reader: usize, writer: usize, size: usize) -> Result<(), &'static str> {
+        if size > CAP {
+            return Err("size exceeds capacity");
+        }
+        if CAP == 0 {
+            if reader != 0 || writer != 0 {
+                return Err("index out of range");
+            }
+        } else {
+            if reader >= CAP || writer >= CAP {
+                return Err("index out of range");
+            }
+            if writer != (reader + size) % CAP {
+                return Err("writer does not match reader and size");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the internal invariants of the buffer,
+    /// as `from_parts` does for raw components.
+    ///
+    /// # Arguments
+    ///
+    /// # Errors
+    /// Returns an error describing the first broken invariant.
+    ///
+    fn validate(&self) -> Result<(), &'static str> {
+        Self::check_positions(self.reader, self.writer, self.size)?;
+
+        Ok(())
+    }
+
+    /// Decomposes the Ring Buffer into its raw components,
+    /// in the order expected by `from_parts`:
+    /// `(data, reader, writer, size)`.
+    /// The layout is not normalized, the oldest element
+    /// sits at `reader` which can be anywhere in `data`.
+    ///
+    /// # Arguments
+    ///
+    pub fn into_parts(self) -> ([Option<T>; CAP], usize, usize, usize) {
+        // The live elements are moved out, they must not be dropped with the buffer
+        let this = ManuallyDrop::new(self);
+
+        let data = core::array::from_fn(|i| {
+            if this.is_live(i) {
+                // SAFETY: the slot is live and is read only once
+                Some(unsafe { this.data[i].assume_init_read() })
+            } else {
+                None
+            }
+        });
+
+        (data, this.reader, this.writer, this.size)
+    }
+
+    /// Moves the elements into a buffer of capacity `SMALL`,
+    /// keeping their order, the overflow policy and the overwrite count.
+    ///
+    /// # Arguments
+    ///
+    /// # Errors
+    /// Returns the buffer unchanged in `Err` if it holds
+    /// more than `SMALL` elements.
+    ///
+    /// # Examples
+    /// `let small: RingBuff<i32, 4> = buffer.try_into_capacity().unwrap();`
+    ///
+    pub fn try_into_capacity<const SMALL: usize>(mut self) -> Result<RingBuff<T, SMALL>, Self> {
+        if self.len() > SMALL {
+            return Err(self);
+        }
+
+        let mut small = RingBuff::with_policy(self.policy);
+        small.overwrites = self.overwrites;
+        let front_id = self.front_id;
+
+        while let Some(element) = self.pop() {
+            small.push_back(element);
         }
+        small.front_id = front_id;
+
+        Ok(small)
     }
 
     /// Pushes one element to the back of the queue.
+    /// When the buffer is full, the overflow policy decides
+    /// whether the oldest element or the new one is dropped.
+    /// With a capacity of 0 the element is always dropped.
     ///
     /// # Arguments
     /// * `element` - The element to add to the queue
     pub fn push_back(&mut self, element: T) {
+        self.push_back_with_evicted(element);
+    }
+
+    /// Pushes one element to the back of the queue and
+    /// returns the element discarded to make room, if any.
+    /// This is the oldest element with `OverflowPolicy::Overwrite`
+    /// and the pushed element itself with `OverflowPolicy::Reject`.
+    /// A buffer with a capacity of 0 always gives the element back.
+    ///
+    /// # Arguments
+    /// * `element` - The element to add to the queue
+    ///
+    pub fn push_back_with_evicted(&mut self, element: T) -> Option<T> {
         // When reaching the end of the allocated data sequence,
         // the data is written on the first cell
 
-        if self.is_full() { self.reader = self.next_index(self.reader); }
+        if CAP == 0 {
+            return Some(element);
+        }
+
+        let mut evicted = None;
+
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Overwrite => {
+                    // SAFETY: the buffer is full, the reader slot is live
+                    evicted = Some(unsafe { self.data[self.reader].assume_init_read() });
+                    self.reader = self.next_index(self.reader);
+                    self.overwrites += 1;
+                    self.front_id = self.front_id.wrapping_add(1);
+                }
+                OverflowPolicy::Reject => return Some(element),
+            }
+        }
 
-        self.data[self.writer] = Some(element);
+        self.data[self.writer].write(element);
+        self.pushes += 1;
 
         self.size += !self.is_full() as usize;
         self.writer = self.next_index(self.writer);
+
+        evicted
     }
 
-    /// Remove one element from the back of the queue
-    /// and returns it.
+    /// Pushes elements to the back of the queue and returns an
+    /// iterator over the elements discarded along the way, as
+    /// `push_back_with_evicted` would return them.
+    /// The pushes happen lazily while the returned iterator is
+    /// consumed, nothing is pushed if it is dropped right away.
     ///
     /// # Arguments
+    /// * `elements` - The elements to add to the queue
     ///
-    pub fn pop(&mut self) -> Option<T> {
-        let reader = self.reader;
-        self.reader = self.next_index(self.reader);
-        self.size -= 1;
-        mem::take(&mut self.data[reader])
+    pub fn extend_with_evicted<'a, I>(&'a mut self, elements: I) -> impl Iterator<Item = T> + 'a
+        where
+            I: IntoIterator<Item = T>,
+            I::IntoIter: 'a,
+    {
+        elements.into_iter().filter_map(move |element| self.push_back_with_evicted(element))
     }
 
-    /// Returns the index of the next element in data.
+    /// Moves all the elements of `other` to the back of the queue,
+    /// from the oldest to the newest, leaving `other` empty.
+    /// The elements are pushed with `push_back`: when the buffer
+    /// fills up, the overflow policy decides which ones are dropped.
     ///
     /// # Arguments
+    /// * `other` - The buffer to empty into this one
     ///
-    /// * `index` - The original index
+    pub fn append(&mut self, other: &mut RingBuff<T, CAP>) {
+        while let Some(element) = other.pop() {
+            self.push_back(element);
+        }
+    }
+
+    /// Pushes the elements of a slice to the back of the queue,
+    /// with the same result as calling `push_back` on each of them.
+    /// The elements are copied in at most two runs of the data array
+    /// instead of one at a time, and with `OverflowPolicy::Overwrite`
+    /// only the last `CAP` of them are copied.
     ///
-    pub(crate) fn next_index(&self, index: usize) -> usize {
-        if index == CAP - 1 {
-            0
-        } else {
-            index + 1
+    /// # Arguments
+    /// * `src` - The elements to add to the queue
+    ///
+    pub fn copy_from_slice(&mut self, src: &[T])
+        where
+            T: Copy,
+    {
+        let src = match self.policy {
+            OverflowPolicy::Overwrite => src,
+            OverflowPolicy::Reject => &src[..src.len().min(CAP - self.len())],
+        };
+        let total = self.len() + src.len();
+        let evicted = total.saturating_sub(CAP);
+        let skipped = src.len().saturating_sub(CAP);
+        let src = &src[skipped..];
+        if src.is_empty() {
+            return;
         }
+
+        let start = (self.writer + skipped) % CAP;
+        let first = src.len().min(CAP - start);
+        let data = self.data.as_mut_ptr() as *mut T;
+        // SAFETY: both runs are within the data array, `T: Copy`
+        // so the live elements being overwritten need no drop
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), data.add(start), first);
+            ptr::copy_nonoverlapping(src.as_ptr().add(first), data, src.len() - first);
+        }
+
+        self.size = total.min(CAP);
+        self.writer = (start + src.len()) % CAP;
+        self.reader = (self.writer + CAP - self.size) % CAP;
+        self.overwrites += evicted as u64;
+        self.pushes += (skipped + src.len()) as u64;
+        self.front_id = self.front_id.wrapping_add(evicted as u64);
     }
 
-    /// Returns the index of the previous element in data.
+    /// Pushes one element to the back of the queue if
+    /// the buffer is not full, otherwise gives it back
+    /// instead of overwriting the oldest element.
     ///
     /// # Arguments
+    /// * `element` - The element to add to the queue
     ///
-    /// * `index` - The original index
+    /// # Errors
+    /// Returns `Err(element)` if the buffer is full.
     ///
-    fn previous_index(&self, index: usize) -> usize {
-        if index == 0 {
-            CAP - 1
-        } else {
-            index - 1
+    pub fn try_push_back(&mut self, element: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(element);
         }
+
+        self.push_back(element);
+
+        Ok(())
     }
 
-    /// Converts an index from the reader to its corresponding
-    /// index in the data array
+    /// Pushes one element to the back of the queue, never
+    /// dropping an element whatever the overflow policy.
+    /// Same as `try_push_back`, with an error type.
     ///
     /// # Arguments
+    /// * `element` - The element to add to the queue
     ///
-    /// * `index` - The original index
+    /// # Errors
+    /// Returns a `CapacityError` holding the element if the buffer is full.
     ///
-    fn relative_to_absolute_index(&self, index: usize) -> Option<usize> {
-        if index >= self.len() {
-            None
-        } else if self.reader + index >= self.capacity() {
-            Some((self.reader + index) % self.capacity())
-        } else {
-            Some(self.reader + index)
-        }
+    pub fn checked_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.try_push_back(element).map_err(|element| CapacityError { element })
     }
 
-    /// Retains only elements fitting a predicate.
+    /// Pushes one element to the front of the queue if
+    /// the buffer is not full, otherwise gives it back.
     ///
     /// # Arguments
+    /// * `element` - The element to add to the queue
     ///
-    ///  * `f` - A predicate
+    /// # Errors
+    /// Returns `Err(element)` if the buffer is full.
     ///
-    pub fn retain<F>(&mut self, mut f: F)
-        where
-            F: FnMut(&T) -> bool,
-    {
-        self.retain_mut(|elem| f(elem));
+    pub fn try_push_front(&mut self, element: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(element);
+        }
+
+        self.reader = self.previous_index(self.reader);
+        self.data[self.reader].write(element);
+        self.size += 1;
+        self.pushes += 1;
+        self.front_id = self.front_id.wrapping_sub(1);
+
+        Ok(())
     }
 
-    /// Retains only elements fitting a predicate,
-    /// passing a mutable reference to it.
+    /// Remove one element from the back of the queue
+    /// and returns it.
     ///
     /// # Arguments
     ///
-    ///  * `f` - A predicate
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let reader = self.reader;
+        self.reader = self.next_index(self.reader);
+        self.size -= 1;
+        self.front_id = self.front_id.wrapping_add(1);
+        // SAFETY: the slot was live, it is now outside of the live range
+        Some(unsafe { self.data[reader].assume_init_read() })
+    }
+
+    /// Removes the element at a position and returns it,
+    /// or None if the index is out of bounds.
+    /// The elements on the shorter side of the removed one
+    /// are moved by one slot to close the gap.
     ///
-    pub fn retain_mut<F>(&mut self, mut f: F)
-        where
-            F: FnMut(&mut T) -> bool,
-    {
-        let mut size = self.len();
-        let mut j = self.len();
-
-        for i in 0..self.len() {
-            if !f(self.get_mut(i).unwrap()) {
-                self.data[self.relative_to_absolute_index(i).unwrap()] = None;
-                self.writer = self.previous_index(self.writer);
-                size -= 1;
-                if j == self.len() { j = i; };
-            } else if j != self.len() && self.get(i).is_some() {
-                if f(self.get_mut(i).unwrap()) {
-                    let idx = self.relative_to_absolute_index(i).unwrap();
-                    let jdx = self.relative_to_absolute_index(j).unwrap();
-                    self.data.swap(idx, jdx);
-                    j += 1;
-                }
+    /// # Arguments
+    /// * `index` - Position of the element to remove
+    ///
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let removed_at = self.relative_to_absolute_index(index)?;
+        // SAFETY: the slot is live, the gap is closed below
+        let element = unsafe { self.data[removed_at].assume_init_read() };
+
+        let mut gap = removed_at;
+        if index < self.len() / 2 {
+            // Move the older elements one slot towards the back
+            for _ in 0..index {
+                let previous = self.previous_index(gap);
+                // SAFETY: the previous slot is live, `gap` was moved out
+                let moved = unsafe { self.data[previous].assume_init_read() };
+                self.data[gap].write(moved);
+                gap = previous;
             }
+            self.reader = self.next_index(self.reader);
+        } else {
+            // Move the newer elements one slot towards the front
+            for _ in index + 1..self.len() {
+                let next = self.next_index(gap);
+                // SAFETY: the next slot is live, `gap` was moved out
+                let moved = unsafe { self.data[next].assume_init_read() };
+                self.data[gap].write(moved);
+                gap = next;
+            }
+            self.writer = self.previous_index(self.writer);
+        }
+        if index == 0 {
+            self.front_id = self.front_id.wrapping_add(1);
         }
+        self.size -= 1;
 
-        self.size = size;
+        Some(element)
     }
 
-    /// Removes all elements in the buffer.
-    /// Note that this method has no effect on
-    /// the allocated capacity of the buffer.
+    /// Inserts an element at a position of a buffer which is not
+    /// full. The elements on the shorter side of the position are
+    /// moved by one slot to make room.
     ///
     /// # Arguments
+    /// * `index` - Position of the new element, at most the length
+    /// * `element` - The element to insert
     ///
-    pub fn clear(&mut self) {
-        for _ in 0..self.size {
+    fn insert(&mut self, index: usize, element: T) {
+        debug_assert!(!self.is_full() && index <= self.len());
+
+        let mut gap;
+        if index < self.len() - index {
+            // Move the older elements one slot towards the front
+            self.reader = self.previous_index(self.reader);
+            gap = self.reader;
+            for _ in 0..index {
+                let next = self.next_index(gap);
+                // SAFETY: the next slot is live, `gap` was moved out or free
+                let moved = unsafe { self.data[next].assume_init_read() };
+                self.data[gap].write(moved);
+                gap = next;
+            }
+        } else {
+            // Move the newer elements one slot towards the back
+            gap = self.writer;
+            for _ in index..self.len() {
+                let previous = self.previous_index(gap);
+                // SAFETY: the previous slot is live, `gap` was moved out or free
+                let moved = unsafe { self.data[previous].assume_init_read() };
+                self.data[gap].write(moved);
+                gap = previous;
+            }
+            self.writer = self.next_index(self.writer);
+        }
+
+        self.data[gap].write(element);
+        self.size += 1;
+        self.pushes += 1;
+    }
+
+    /// Inserts an element into a sorted buffer at the position
+    /// found by `binary_search`, so that the buffer stays sorted,
+    /// and returns that position.
+    /// Whatever the overflow policy, a full buffer first drops its
+    /// smallest element, the oldest one, like an overwriting
+    /// `push_back`. A buffer with a capacity of 0 drops the element.
+    ///
+    /// # Arguments
+    /// * `element` - The element to insert
+    ///
+    pub fn insert_sorted(&mut self, element: T) -> usize
+        where
+            T: Ord,
+    {
+        if CAP == 0 {
+            return 0;
+        }
+
+        if self.is_full() {
             self.pop();
+            self.overwrites += 1;
         }
+
+        let index = match self.binary_search(&element) {
+            Ok(index) | Err(index) => index,
+        };
+        self.insert(index, element);
+
+        index
     }
 
-    /// Returns true if the buffer contains no elements.
+    /// Removes the oldest element equal to `x` and returns it,
+    /// or None if no element is equal to `x`.
+    /// The order of the remaining elements is kept.
     ///
     /// # Arguments
+    /// * `x` - The value to look for
     ///
-    pub const fn is_empty(&self) -> bool {
-        self.size == 0
+    pub fn remove_first_matching(&mut self, x: &T) -> Option<T>
+        where
+            T: PartialEq,
+    {
+        let index = self.position(|element| element == x)?;
+        self.remove(index)
     }
 
-    /// Returns whether or not the buffer is full.
+    /// Returns the index of the next element in data.
     ///
     /// # Arguments
     ///
-    const fn is_full(&self) -> bool {
-        self.size == CAP
+    /// * `index` - The original index
+    ///
+    pub(crate) fn next_index(&self, index: usize) -> usize {
+        wrap::next(index, CAP)
     }
 
-    /// Returns the number of elements in the buffer.
+    /// Returns the index of the previous element in data.
     ///
     /// # Arguments
     ///
-    const fn len(&self) -> usize {
-        self.size
+    /// * `index` - The original index
+    ///
+    fn previous_index(&self, index: usize) -> usize {
+        wrap::previous(index, CAP)
     }
 
-    /// Returns the maximum number of elements the
-    /// buffer can hold.
+    /// Returns true if the slot at an index of the data
+    /// array holds one of the elements of the queue.
     ///
     /// # Arguments
     ///
-    pub const fn capacity(&self) -> usize {
-        CAP
+    /// * `index` - The index in the data array
+    ///
+    fn is_live(&self, index: usize) -> bool {
+        index < CAP && (index + CAP - self.reader) % CAP < self.len()
     }
 
-    /// Returns a reference to an element or None
-    /// if the index is out of bounds.
+    /// Returns a reference to the element stored at an index of
+    /// the data array, or None if the slot is not live.
     ///
     /// # Arguments
-    /// * `index` - Position of the element to look up
     ///
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index >= self.len() {
-            None
+    /// * `index` - The index in the data array
+    ///
+    fn slot(&self, index: usize) -> Option<&T> {
+        if self.is_live(index) {
+            // SAFETY: live slots are initialized
+            Some(unsafe { self.data[index].assume_init_ref() })
         } else {
-            let i = self.relative_to_absolute_index(index).expect("Index is valid.");
-            self.data[i].as_ref()
+            None
         }
     }
 
-    /// Returns a mutable reference to an element or None
-    /// if the index is out of bounds
+    /// Returns the live elements as two slices: the first from
+    /// the reader onward, the second from the start of the data
+    /// array, with the lengths given by `segment_lengths`.
     ///
     /// # Arguments
-    /// * `index` - Position of the element to look up
     ///
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index >= self.len() {
-            None
-        } else {
-            let i = self.relative_to_absolute_index(index).expect("Index is valid.");
-            self.data[i].as_mut()
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.segment_lengths();
+        let data = self.data.as_ptr() as *const T;
+
+        // SAFETY: both runs are made of live slots
+        unsafe {
+            (
+                core::slice::from_raw_parts(data.add(self.reader), first),
+                core::slice::from_raw_parts(data, second),
+            )
         }
     }
 
-    /// Returns an iterator on the buffer
+    /// Returns the live elements as two mutable slices,
+    /// split like `as_slices`.
     ///
     /// # Arguments
     ///
-    pub fn iter(&self) -> RingBuffIter<T, CAP> {
-        RingBuffIter {
-            buffer: &self,
-            index: self.reader,
-            count: 0,
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (first, second) = self.segment_lengths();
+        let data = self.data.as_mut_ptr() as *mut T;
+
+        // SAFETY: both runs are made of live slots and do not overlap
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(data.add(self.reader), first),
+                core::slice::from_raw_parts_mut(data, second),
+            )
         }
     }
-}
 
-pub struct RingBuffIter<'a, T, const CAP: usize> {
-    /// A reference to the RingBuff
-    buffer: &'a RingBuff<T, CAP>,
-    /// The index of the iterator in the buffer data array
+    /// Returns a raw pointer to the oldest element, for handing
+    /// the buffer over to C code or a DMA transfer without copying.
+    /// The `len()` elements are only contiguous after a call to
+    /// `make_contiguous` or when `is_contiguous` is true, otherwise
+    /// only the first run given by `segment_lengths` starts at this
+    /// pointer.
+    /// The pointer is valid for reading the live elements as long as
+    /// the buffer is neither moved nor modified. Reading past the first
+    /// run of a buffer that is not contiguous is undefined behavior.
+    ///
+    /// # Arguments
+    ///
+    pub fn as_ptr(&self) -> *const T {
+        // SAFETY: the reader is within the data array, or 0
+        unsafe { (self.data.as_ptr() as *const T).add(self.reader) }
+    }
+
+    /// Returns a raw mutable pointer to the oldest element,
+    /// with the requirements of `as_ptr`.
+    /// Writes through the pointer must stay within the live elements
+    /// and replace them with valid values, the buffer drops them later.
+    ///
+    /// # Arguments
+    ///
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        // SAFETY: the reader is within the data array, or 0
+        unsafe { (self.data.as_mut_ptr() as *mut T).add(self.reader) }
+    }
+
+    /// Returns an iterator on the non-empty contiguous runs of
+    /// elements in the data array, from the oldest run to the
+    /// newest: none if the buffer is empty, two if it wraps.
+    ///
+    /// # Arguments
+    ///
+    pub fn segments(&self) -> impl Iterator<Item = &[T]> {
+        let (first, second) = self.as_slices();
+
+        [first, second].into_iter().filter(|segment| !segment.is_empty())
+    }
+
+    /// Renders the data array slot by slot, for debugging: live
+    /// slots show their element and the others `_`, the reader is
+    /// marked `R:` and the writer `W:`, e.g. `[_, R:100, 101, W:_, _]`.
+    ///
+    /// # Arguments
+    ///
+    #[cfg(feature = "std")]
+    pub fn debug_layout(&self) -> String
+        where
+            T: Debug,
+    {
+        let slots: Vec<String> = (0..CAP)
+            .map(|index| {
+                let reader = if index == self.reader { "R" } else { "" };
+                let writer = if index == self.writer { "W" } else { "" };
+                let marker = if reader.is_empty() && writer.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}:", reader, writer)
+                };
+                match self.slot(index) {
+                    Some(element) => format!("{}{:?}", marker, element),
+                    None => format!("{}_", marker),
+                }
+            })
+            .collect();
+
+        format!("[{}]", slots.join(", "))
+    }
+
+    /// Converts an index from the reader to its corresponding
+    /// index in the data array
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The original index
+    ///
+    fn relative_to_absolute_index(&self, index: usize) -> Option<usize> {
+        if index >= self.len() {
+            None
+        } else {
+            Some(wrap::add(self.reader, index, CAP))
+        }
+    }
+
+    /// Retains only elements fitting a predicate.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///
+    pub fn retain<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    /// Retains only elements fitting a predicate
+    /// and returns the number of removed elements.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///
+    pub fn retain_count<F>(&mut self, f: F) -> usize
+        where
+            F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        self.retain(f);
+        len - self.len()
+    }
+
+    /// Retains only elements fitting a predicate,
+    /// passing a mutable reference to it.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///
+    pub fn retain_mut<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&mut T) -> bool,
+    {
+        self.retain_with(|_, element| f(element), drop);
+    }
+
+    /// Retains only elements fitting a predicate, passing
+    /// it the position of the element and a mutable reference
+    /// to it.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///
+    pub fn retain_mut_indexed<F>(&mut self, f: F)
+        where
+            F: FnMut(usize, &mut T) -> bool,
+    {
+        self.retain_with(f, drop);
+    }
+
+    /// Retains only the elements whose position is set in a mask,
+    /// `keep[i]` telling whether the element at position `i` is kept.
+    /// The mask is applied in a single pass.
+    ///
+    /// # Arguments
+    ///
+    ///  * `keep` - One flag per element, from the oldest to the newest
+    ///
+    /// # Panics
+    /// Panics if the length of `keep` is not the length of the buffer.
+    ///
+    pub fn retain_mask(&mut self, keep: &[bool]) {
+        assert_eq!(keep.len(), self.len(), "Mask length does not match buffer length.");
+
+        self.retain_with(|i, _| keep[i], drop);
+    }
+
+    /// Retains only elements fitting a fallible predicate.
+    /// Stops calling the predicate at its first error, which is
+    /// returned: the elements already checked are pruned and the
+    /// remaining ones are all kept.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A fallible predicate
+    ///
+    /// # Errors
+    /// Returns the first error returned by `f`.
+    ///
+    pub fn try_retain<E, F>(&mut self, mut f: F) -> Result<(), E>
+        where
+            F: FnMut(&T) -> Result<bool, E>,
+    {
+        let mut error = None;
+
+        self.retain_with(
+            |_, element| {
+                if error.is_some() {
+                    return true;
+                }
+                f(element).unwrap_or_else(|e| {
+                    error = Some(e);
+                    true
+                })
+            },
+            drop,
+        );
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Retains only elements fitting a predicate,
+    /// passing a mutable reference to it, and appends
+    /// the removed elements to `removed` in order.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///  * `removed` - Where removed elements are appended
+    ///
+    #[cfg(feature = "std")]
+    pub fn retain_mut_into<F>(&mut self, mut f: F, removed: &mut Vec<T>)
+        where
+            F: FnMut(&mut T) -> bool,
+    {
+        self.retain_with(|_, element| f(element), |element| removed.push(element));
+    }
+
+    /// Retains only elements fitting a predicate, calling it
+    /// from the newest element to the oldest.
+    /// The kept elements stay in their original order.
+    /// If `f` panics the elements it did not check are kept.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate
+    ///
+    pub fn retain_rev<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&T) -> bool,
+    {
+        // Mirror of `retain_with`: the survivors are moved towards the writer
+        let len = self.len();
+        let writer = self.writer;
+        let mut removed_front = 0;
+        let mut guard = RetainGuard {
+            reader: writer,
+            read: writer,
+            write: writer,
+            kept: 0,
+            remaining: len,
+            backward: true,
+            buffer: self,
+        };
+
+        for _ in 0..len {
+            let read = guard.buffer.previous_index(guard.read);
+            // SAFETY: the slots before `read` were live before the pass
+            let keep = f(unsafe { guard.buffer.data[read].assume_init_ref() });
+            guard.read = read;
+            guard.remaining -= 1;
+
+            if keep {
+                guard.write = guard.buffer.previous_index(guard.write);
+                if read != guard.write {
+                    // SAFETY: `read` is live and `write` was moved out
+                    let element = unsafe { guard.buffer.data[read].assume_init_read() };
+                    guard.buffer.data[guard.write].write(element);
+                }
+                guard.kept += 1;
+                removed_front = 0;
+            } else {
+                if guard.kept == 0 {
+                    guard.write = read;
+                }
+                removed_front += 1;
+                // SAFETY: `read` is live, it is dropped only once
+                unsafe { guard.buffer.data[read].assume_init_drop() };
+            }
+        }
+
+        guard.buffer.front_id = guard.buffer.front_id.wrapping_add(removed_front);
+    }
+
+    /// Removes consecutive equal elements, keeping the first
+    /// of each run, like `Vec::dedup`.
+    ///
+    /// # Arguments
+    ///
+    pub fn dedup(&mut self)
+        where
+            T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive elements mapping to the same key,
+    /// keeping the first of each run.
+    ///
+    /// # Arguments
+    ///
+    ///  * `key` - Returns the key compared between neighbours
+    ///
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+        where
+            K: PartialEq,
+            F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns
+    /// true, keeping the first of each run. Like `Vec::dedup_by`,
+    /// `same_bucket` receives the element being checked, then the
+    /// last kept one.
+    /// If `same_bucket` panics the elements it did not check are kept.
+    ///
+    /// # Arguments
+    ///
+    ///  * `same_bucket` - Tells whether an element duplicates the previous one
+    ///
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+        where
+            F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        let reader = self.reader;
+        let mut guard = RetainGuard {
+            reader,
+            read: reader,
+            write: reader,
+            kept: 0,
+            remaining: len,
+            backward: false,
+            buffer: self,
+        };
+
+        for _ in 0..len {
+            let read = guard.read;
+            let duplicate = guard.kept > 0 && {
+                let previous = guard.buffer.previous_index(guard.write);
+                let data = guard.buffer.data.as_mut_ptr();
+                // SAFETY: `read` was live before the pass and `previous`
+                // is the last kept element, the two slots are distinct
+                unsafe { same_bucket((*data.add(read)).assume_init_mut(), (*data.add(previous)).assume_init_mut()) }
+            };
+            guard.read = guard.buffer.next_index(read);
+            guard.remaining -= 1;
+
+            if duplicate {
+                // SAFETY: `read` is live, it is dropped only once
+                unsafe { guard.buffer.data[read].assume_init_drop() };
+            } else {
+                if read != guard.write {
+                    // SAFETY: `read` is live and `write` was moved out
+                    let element = unsafe { guard.buffer.data[read].assume_init_read() };
+                    guard.buffer.data[guard.write].write(element);
+                }
+                guard.write = guard.buffer.next_index(guard.write);
+                guard.kept += 1;
+            }
+        }
+    }
+
+    /// Returns an iterator removing the elements fitting a predicate
+    /// and yielding them by value, from the oldest to the newest.
+    /// The other elements stay in the buffer in their original order.
+    /// Dropping the iterator early keeps the elements it did not check.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate, true for the elements to remove
+    ///
+    pub fn extract_if<P>(&mut self, pred: P) -> ExtractIf<'_, T, P, CAP>
+        where
+            P: FnMut(&mut T) -> bool,
+    {
+        let len = self.len();
+        let reader = self.reader;
+        // If the iterator is leaked, the elements are leaked
+        // with it instead of being seen twice
+        self.size = 0;
+
+        ExtractIf {
+            guard: RetainGuard {
+                reader,
+                read: reader,
+                write: reader,
+                kept: 0,
+                remaining: len,
+                backward: false,
+                buffer: self,
+            },
+            pred,
+        }
+    }
+
+    /// Retains only elements fitting a predicate in a single pass,
+    /// moving the survivors towards the reader and handing the
+    /// removed elements to `removed`.
+    /// Elements removed from the front only advance the reader,
+    /// so dropping a stale prefix moves no data.
+    /// If `f` or `removed` panics the elements `f` did not check
+    /// are kept, so the buffer stays consistent.
+    ///
+    /// # Arguments
+    ///
+    ///  * `f` - A predicate receiving the position of the element
+    ///  * `removed` - Receives each removed element
+    ///
+    fn retain_with<F, R>(&mut self, mut f: F, mut removed: R)
+        where
+            F: FnMut(usize, &mut T) -> bool,
+            R: FnMut(T),
+    {
+        let len = self.len();
+        let reader = self.reader;
+        let mut guard = RetainGuard {
+            reader,
+            read: reader,
+            write: reader,
+            kept: 0,
+            remaining: len,
+            backward: false,
+            buffer: self,
+        };
+
+        for i in 0..len {
+            let read = guard.read;
+            // SAFETY: the slots from `read` were live before the pass
+            let keep = f(i, unsafe { guard.buffer.data[read].assume_init_mut() });
+            guard.read = guard.buffer.next_index(read);
+            guard.remaining -= 1;
+
+            if keep {
+                if read != guard.write {
+                    // SAFETY: `read` is live and `write` was moved out
+                    let element = unsafe { guard.buffer.data[read].assume_init_read() };
+                    guard.buffer.data[guard.write].write(element);
+                }
+                guard.write = guard.buffer.next_index(guard.write);
+                guard.kept += 1;
+            } else {
+                // SAFETY: `read` is live, it is moved out only once
+                let element = unsafe { guard.buffer.data[read].assume_init_read() };
+                if guard.kept == 0 {
+                    guard.reader = guard.read;
+                    guard.write = guard.read;
+                    guard.buffer.front_id = guard.buffer.front_id.wrapping_add(1);
+                }
+                removed(element);
+            }
+        }
+
+        drop(guard);
+        debug_assert_eq!(self.validate(), Ok(()));
+    }
+
+    /// Sets the live elements to the `size` slots starting
+    /// from `reader`, the writer following them.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The index of the queue head in the data array
+    /// * `size` - Number of elements in the queue
+    ///
+    fn set_live_run(&mut self, reader: usize, size: usize) {
+        self.reader = reader;
+        self.size = size;
+        self.writer = if reader + size >= CAP {
+            reader + size - CAP
+        } else {
+            reader + size
+        };
+    }
+
+    /// Removes all elements in the buffer.
+    /// Note that this method has no effect on
+    /// the allocated capacity of the buffer.
+    ///
+    /// # Arguments
+    ///
+    pub fn clear(&mut self) {
+        let reader = self.reader;
+        let len = self.len();
+
+        self.reader = 0;
+        self.writer = 0;
+        self.size = 0;
+        self.front_id = self.front_id.wrapping_add(len as u64);
+
+        for i in 0..len {
+            let index = (reader + i) % CAP;
+            // SAFETY: the slot was live, the buffer is already empty
+            unsafe { self.data[index].assume_init_drop() };
+        }
+    }
+
+    /// Shortens the buffer, keeping the `len` oldest
+    /// elements and dropping the newest ones.
+    /// Has no effect if `len` is greater or equal
+    /// to the current length.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The number of elements to keep
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.writer = self.previous_index(self.writer);
+            self.size -= 1;
+            // SAFETY: the slot was live, it is now outside of the live range
+            unsafe { self.data[self.writer].assume_init_drop() };
+        }
+    }
+
+    /// Splits the buffer in two at a position: the elements before
+    /// `at` stay in the buffer, the ones from `at` are moved in order
+    /// to the returned buffer, which has the same overflow policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - Position of the first element to move
+    ///
+    /// # Panics
+    /// Panics if `at` is greater than the length.
+    ///
+    pub fn split_off(&mut self, at: usize) -> RingBuff<T, CAP> {
+        assert!(at <= self.len(), "Split index out of bounds.");
+
+        let mut other = RingBuff::with_policy(self.policy);
+
+        for i in at..self.len() {
+            let index = self.relative_to_absolute_index(i).expect("Index is valid.");
+            // SAFETY: the slot is live, it leaves the live run below
+            other.push_back(unsafe { self.data[index].assume_init_read() });
+        }
+        self.set_live_run(self.reader, at);
+
+        other
+    }
+
+    /// Returns two buffers with clones of the elements fitting
+    /// a predicate and of the other ones, both in the original
+    /// order and with the same overflow policy.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    pub fn partition<P>(&self, mut pred: P) -> (RingBuff<T, CAP>, RingBuff<T, CAP>)
+        where
+            T: Clone,
+            P: FnMut(&T) -> bool,
+    {
+        let mut matching = RingBuff::with_policy(self.policy);
+        let mut others = RingBuff::with_policy(self.policy);
+
+        for element in self.iter() {
+            if pred(element) {
+                matching.push_back(element.clone());
+            } else {
+                others.push_back(element.clone());
+            }
+        }
+
+        (matching, others)
+    }
+
+    /// Removes elements from the front of the queue as long
+    /// as they fit a predicate and returns them in order.
+    /// Stops at the first element not fitting the predicate.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    #[cfg(feature = "std")]
+    pub fn drain_while_front<P>(&mut self, mut pred: P) -> Vec<T>
+        where
+            P: FnMut(&T) -> bool,
+    {
+        let mut drained = Vec::new();
+
+        while self.get(0).is_some_and(&mut pred) {
+            drained.extend(self.pop());
+        }
+
+        drained
+    }
+
+    /// Rotates the elements `n` positions to the left,
+    /// the element at index `n` becoming the oldest.
+    /// Runs in O(1) when the buffer is full. Otherwise the
+    /// elements are rotated in place when they are contiguous,
+    /// or `n` elements are moved from the front to the back.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of positions, taken modulo the length
+    ///
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let n = n % self.len();
+
+        if self.is_full() {
+            self.reader = (self.reader + n) % CAP;
+            self.writer = self.reader;
+        } else if self.reader + self.len() <= CAP {
+            self.as_mut_slices().0.rotate_left(n);
+        } else {
+            self.rotate_left_moving(n);
+        }
+    }
+
+    /// Rotates the elements `n` positions to the left by moving
+    /// `n` elements from the front to the back, one at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of positions, lower than the length
+    ///
+    fn rotate_left_moving(&mut self, n: usize) {
+        for _ in 0..n {
+            // SAFETY: the reader slot is live, it is moved to the writer slot
+            let element = unsafe { self.data[self.reader].assume_init_read() };
+            self.reader = self.next_index(self.reader);
+            self.data[self.writer].write(element);
+            self.writer = self.next_index(self.writer);
+        }
+    }
+
+    /// Rotates the elements `n` positions to the right,
+    /// the element at index `len - n` becoming the oldest.
+    /// Runs in O(1) when the buffer is full. Otherwise the
+    /// elements are rotated in place when they are contiguous,
+    /// or `n` elements are moved from the back to the front.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of positions, taken modulo the length
+    ///
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let n = n % self.len();
+
+        if self.is_full() {
+            self.reader = (self.reader + CAP - n) % CAP;
+            self.writer = self.reader;
+        } else if self.reader + self.len() <= CAP {
+            self.as_mut_slices().0.rotate_right(n);
+        } else {
+            self.rotate_right_moving(n);
+        }
+    }
+
+    /// Rotates the elements `n` positions to the right by moving
+    /// `n` elements from the back to the front, one at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of positions, lower than the length
+    ///
+    fn rotate_right_moving(&mut self, n: usize) {
+        for _ in 0..n {
+            self.writer = self.previous_index(self.writer);
+            // SAFETY: the slot before the writer is live, it is moved before the reader
+            let element = unsafe { self.data[self.writer].assume_init_read() };
+            self.reader = self.previous_index(self.reader);
+            self.data[self.reader].write(element);
+        }
+    }
+
+    /// Swaps the elements at positions `a` and `b`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Position of the first element
+    /// * `b` - Position of the second element
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` is out of bounds.
+    ///
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let a = self.relative_to_absolute_index(a).expect("Index out of bounds.");
+        let b = self.relative_to_absolute_index(b).expect("Index out of bounds.");
+        self.data.swap(a, b);
+    }
+
+    /// Swaps the elements at positions `i` and `j` if both are
+    /// in bounds, and returns whether they were swapped.
+    /// Unlike `swap`, never panics.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Position of the first element
+    /// * `j` - Position of the second element
+    ///
+    pub fn try_swap(&mut self, i: usize, j: usize) -> bool {
+        match (self.relative_to_absolute_index(i), self.relative_to_absolute_index(j)) {
+            (Some(i), Some(j)) => {
+                self.data.swap(i, j);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reverses the order of the elements in place,
+    /// the oldest element becoming the newest.
+    ///
+    /// # Arguments
+    ///
+    pub fn reverse(&mut self) {
+        let len = self.len();
+
+        for i in 0..len / 2 {
+            let front = self.relative_to_absolute_index(i).expect("Index is valid.");
+            let back = self.relative_to_absolute_index(len - 1 - i).expect("Index is valid.");
+            self.data.swap(front, back);
+        }
+    }
+
+    /// Clears the buffer and fills every slot with clones of
+    /// `value`, the oldest element sitting at the start of the
+    /// data array.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to fill the buffer with
+    ///
+    pub fn fill(&mut self, value: T)
+        where
+            T: Clone,
+    {
+        self.clear();
+
+        if CAP > 0 {
+            for _ in 1..CAP {
+                self.push_back(value.clone());
+            }
+            self.push_back(value);
+        }
+    }
+
+    /// Clears the buffer and fills every slot with the values
+    /// returned by `f`, called once per slot from the oldest
+    /// element to the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The generator of the elements
+    ///
+    pub fn fill_with<F>(&mut self, mut f: F)
+        where
+            F: FnMut() -> T,
+    {
+        self.clear();
+
+        for _ in 0..CAP {
+            self.push_back(f());
+        }
+    }
+
+    /// Clears the buffer and fills every slot by cycling
+    /// through the given elements.
+    /// The buffer stays empty if `iter` yields nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - The pattern to repeat
+    ///
+    pub fn fill_cycling<I>(&mut self, iter: I)
+        where
+            I: IntoIterator<Item = T>,
+            I::IntoIter: Clone,
+    {
+        self.clear();
+
+        for element in iter.into_iter().cycle().take(CAP) {
+            self.push_back(element);
+        }
+    }
+
+    /// Moves the elements so that the oldest one sits at the start
+    /// of the data array, the live elements being contiguous.
+    /// Only live elements are moved, so slots that were never
+    /// written are not read.
+    /// Call it before reading the buffer through `as_ptr`.
+    ///
+    /// # Arguments
+    ///
+    pub fn make_contiguous(&mut self) {
+        if self.reader == 0 {
+            return;
+        }
+
+        let (first, second) = self.segment_lengths();
+        let data = self.data.as_mut_ptr();
+
+        // SAFETY: the copies stay in the data array, the source slots
+        // are left outside of the live range
+        unsafe {
+            if second == 0 {
+                ptr::copy(data.add(self.reader), data, first);
+            } else {
+                // Close the gap between the two runs, then swap them
+                ptr::copy(data.add(self.reader), data.add(second), first);
+            }
+        }
+        self.data[..self.size].rotate_left(second);
+
+        self.reader = 0;
+        self.writer = self.size % CAP;
+    }
+
+    /// Moves the elements so that the reader sits at the start of
+    /// the data array, without changing their order.
+    /// Same as `make_contiguous`.
+    ///
+    /// # Arguments
+    ///
+    pub fn normalize(&mut self) {
+        self.make_contiguous();
+    }
+
+    /// Sorts the elements in ascending order.
+    /// The sort is unstable and does not allocate.
+    ///
+    /// # Arguments
+    ///
+    pub fn sort(&mut self)
+        where
+            T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the elements with a comparator function.
+    /// The sort is unstable and does not allocate.
+    ///
+    /// # Arguments
+    ///
+    /// * `compare` - The comparator function
+    ///
+    pub fn sort_by<F>(&mut self, compare: F)
+        where
+            F: FnMut(&T, &T) -> Ordering,
+    {
+        self.make_contiguous();
+
+        self.as_mut_slices().0.sort_unstable_by(compare);
+    }
+
+    /// Sorts the elements with a key extraction function.
+    /// The sort is unstable and does not allocate.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The key extraction function
+    ///
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+        where
+            F: FnMut(&T) -> K,
+            K: Ord,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Binary searches a sorted buffer for a value.
+    /// Returns `Ok` with the index of a matching element, or `Err`
+    /// with the index where the value could be inserted to keep
+    /// the buffer sorted. The result is meaningless if the buffer
+    /// is not sorted.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value to look for
+    ///
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+        where
+            T: Ord,
+    {
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            match self.get(mid).expect("Index is valid.").cmp(x) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(low)
+    }
+
+    /// Returns the lengths of the two contiguous runs of
+    /// elements in the data array: the first from the reader
+    /// onward, the second from the start of the array.
+    /// The second length is 0 when the elements do not wrap.
+    ///
+    /// # Arguments
+    ///
+    pub fn segment_lengths(&self) -> (usize, usize) {
+        let first = self.len().min(CAP - self.reader);
+        (first, self.len() - first)
+    }
+
+    /// Returns true if the elements form a single run
+    /// in the data array, which is always the case when
+    /// the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub const fn is_contiguous(&self) -> bool {
+        self.reader + self.len() <= CAP
+    }
+
+    /// Returns the number of elements overwritten by a push
+    /// since the buffer was created or the count was reset.
+    ///
+    /// # Arguments
+    ///
+    pub const fn overwrite_count(&self) -> u64 {
+        self.overwrites
+    }
+
+    /// Resets the number of overwritten elements to 0.
+    ///
+    /// # Arguments
+    ///
+    pub fn reset_overwrite_count(&mut self) {
+        self.overwrites = 0;
+    }
+
+    /// Moves the reader past the `n` oldest elements without
+    /// moving them out of the data array. Their values stay in place
+    /// until overwritten, so `restore` can bring them back.
+    /// Runs in O(1), the elements need no drop.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements to consume, clamped to the length
+    ///
+    pub fn advance(&mut self, n: usize)
+        where
+            T: Copy,
+    {
+        let n = n.min(self.len());
+        self.reader = wrap::add(self.reader, n, CAP);
+        self.size -= n;
+        self.front_id = self.front_id.wrapping_add(n as u64);
+    }
+
+    /// Saves the current position of the reader and writer.
+    ///
+    /// # Arguments
+    ///
+    pub const fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            reader: self.reader,
+            writer: self.writer,
+            size: self.size,
+            overwrites: self.overwrites,
+            pushes: self.pushes,
+            front_id: self.front_id,
+        }
+    }
+
+    /// Brings the reader and writer back to a saved position,
+    /// making elements consumed with `advance` since the checkpoint
+    /// readable again.
+    /// Only valid if no element was pushed, overwritten or moved out
+    /// of the buffer since the checkpoint was taken: elements pushed
+    /// after the checkpoint are discarded. Pushes and overwrites are
+    /// checked in debug builds.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - The position to restore
+    ///
+    /// # Panics
+    /// Panics if the checkpoint points outside of the buffer.
+    ///
+    /// # Safety
+    /// The checkpoint must have been taken on this buffer, and the
+    /// buffer must not have been replaced since, e.g. by assigning a
+    /// new buffer to it. The restored slots were then live when the
+    /// checkpoint was taken and still hold a value, possibly a stale
+    /// copy, while the slots of another buffer may never have been
+    /// written.
+    ///
+    pub unsafe fn restore(&mut self, checkpoint: Checkpoint)
+        where
+            T: Copy,
+    {
+        assert!(
+            Self::check_positions(checkpoint.reader, checkpoint.writer, checkpoint.size).is_ok(),
+            "The checkpoint does not belong to this buffer."
+        );
+
+        debug_assert_eq!(checkpoint.overwrites, self.overwrites, "Elements were overwritten since the checkpoint.");
+        debug_assert_eq!(checkpoint.pushes, self.pushes, "Elements were pushed since the checkpoint.");
+
+        self.reader = checkpoint.reader;
+        self.writer = checkpoint.writer;
+        self.size = checkpoint.size;
+        self.front_id = checkpoint.front_id;
+    }
+
+    /// Returns true if the buffer contains no elements.
+    ///
+    /// # Arguments
+    ///
+    pub const fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns whether or not the buffer is full.
+    ///
+    /// # Arguments
+    ///
+    const fn is_full(&self) -> bool {
+        self.size == CAP
+    }
+
+    /// Returns the number of elements in the buffer.
+    ///
+    /// # Arguments
+    ///
+    pub const fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of elements the buffer would
+    /// hold after one more `push_back`, capped at the capacity.
+    ///
+    /// # Arguments
+    ///
+    pub const fn len_after_push(&self) -> usize {
+        if self.is_full() {
+            CAP
+        } else {
+            self.len() + 1
+        }
+    }
+
+    /// Returns the maximum number of elements the
+    /// buffer can hold.
+    ///
+    /// # Arguments
+    ///
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of elements that can still be
+    /// pushed before the buffer is full.
+    ///
+    /// # Arguments
+    ///
+    pub const fn remaining_capacity(&self) -> usize {
+        CAP - self.len()
+    }
+
+    /// Returns how full the buffer is, from 0.0 when empty
+    /// to 1.0 when full. A buffer with a capacity of 0 reports 0.0.
+    ///
+    /// # Arguments
+    ///
+    pub fn load_factor(&self) -> f32 {
+        if CAP == 0 {
+            0.0
+        } else {
+            self.len() as f32 / CAP as f32
+        }
+    }
+
+    /// Returns a reference to an element or None
+    /// if the index is out of bounds.
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element to look up
+    ///
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            None
+        } else {
+            let i = self.relative_to_absolute_index(index).expect("Index is valid.");
+            self.slot(i)
+        }
+    }
+
+    /// Returns a mutable reference to an element or None
+    /// if the index is out of bounds
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element to look up
+    ///
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            None
+        } else {
+            let i = self.relative_to_absolute_index(index).expect("Index is valid.");
+            // SAFETY: the index is in bounds, the slot is live
+            Some(unsafe { self.data[i].assume_init_mut() })
+        }
+    }
+
+    /// Returns a reference to the element at `index` modulo the
+    /// length, reading the buffer as a cyclic table, or None
+    /// if the buffer is empty.
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element, wrapping past the newest
+    ///
+    pub fn get_wrapping(&self, index: usize) -> Option<&T> {
+        self.get(index.checked_rem(self.len())?)
+    }
+
+    /// Returns a mutable reference to the element at `index`
+    /// modulo the length, or None if the buffer is empty.
+    ///
+    /// # Arguments
+    /// * `index` - Position of the element, wrapping past the newest
+    ///
+    pub fn get_wrapping_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index.checked_rem(self.len())?)
+    }
+
+    /// Returns mutable references to the elements at `index`
+    /// and `index + 1`, or None if either is out of bounds.
+    ///
+    /// # Arguments
+    /// * `index` - Position of the first element of the pair
+    ///
+    pub fn get_pair_mut(&mut self, index: usize) -> Option<(&mut T, &mut T)> {
+        let first = self.relative_to_absolute_index(index)?;
+        let second = self.relative_to_absolute_index(index.checked_add(1)?)?;
+
+        // SAFETY: both indexes are in bounds, the slots are live
+        unsafe {
+            if first < second {
+                let (low, high) = self.data.split_at_mut(second);
+                Some((low[first].assume_init_mut(), high[0].assume_init_mut()))
+            } else {
+                // The pair crosses the end of the data array
+                let (low, high) = self.data.split_at_mut(first);
+                Some((high[0].assume_init_mut(), low[second].assume_init_mut()))
+            }
+        }
+    }
+
+    /// Returns mutable references to the elements at several
+    /// positions at once, or None if any of the indexes is out of
+    /// bounds or appears twice.
+    ///
+    /// # Arguments
+    /// * `indices` - Positions of the elements to look up
+    ///
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= self.len() || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+
+        let indices = indices.map(|index| self.relative_to_absolute_index(index).expect("Index is valid."));
+        let data = self.data.as_mut_ptr();
+        // SAFETY: the indexes are in bounds and distinct,
+        // each live slot is borrowed only once
+        Some(indices.map(|index| unsafe { (*data.add(index)).assume_init_mut() }))
+    }
+
+    /// Returns a reference to the nth element counting
+    /// from the oldest one, without removing it.
+    /// Same as `get`, named after the peeking semantics.
+    ///
+    /// # Arguments
+    /// * `n` - Position of the element, 0 being the oldest
+    ///
+    pub fn peek_nth(&self, n: usize) -> Option<&T> {
+        self.get(n)
+    }
+
+    /// Returns a reference to the nth element counting
+    /// from the newest one, without removing it.
+    ///
+    /// # Arguments
+    /// * `n` - Position of the element, 0 being the newest
+    ///
+    pub fn peek_nth_back(&self, n: usize) -> Option<&T> {
+        if n >= self.len() {
+            None
+        } else {
+            self.get(self.len() - 1 - n)
+        }
+    }
+
+    /// Returns a reference to the nth most recently pushed element.
+    /// Same as `peek_nth_back`.
+    ///
+    /// # Arguments
+    /// * `n` - Position of the element, 0 being the newest
+    ///
+    pub fn nth_newest(&self, n: usize) -> Option<&T> {
+        self.peek_nth_back(n)
+    }
+
+    /// Returns an iterator on the buffer
+    ///
+    /// # Arguments
+    ///
+    /// # Examples
+    /// The iterator borrows the buffer, so it can not be
+    /// modified until the iteration is over:
+    /// ```compile_fail,E0502
+    /// use circular_buff::RingBuff;
+    ///
+    /// let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+    /// buffer.push_back(100);
+    ///
+    /// let mut iter = buffer.iter();
+    /// buffer.push_back(101);
+    /// iter.next();
+    /// ```
+    ///
+    pub fn iter(&self) -> RingBuffIter<'_, T, CAP> {
+        RingBuffIter {
+            buffer: self,
+            index: self.reader,
+            back: self.writer,
+            remaining: self.len(),
+        }
+    }
+
+    /// Returns an iterator on the elements in a range of positions,
+    /// 0 being the oldest. The end of the range is clamped to the
+    /// length of the buffer.
+    ///
+    /// # Arguments
+    /// * `range` - Positions of the elements
+    ///
+    /// # Panics
+    /// Panics if the start of the range is greater than its end.
+    ///
+    pub fn range(&self, range: Range<usize>) -> RingBuffIter<'_, T, CAP> {
+        assert!(range.start <= range.end, "Range start is greater than range end.");
+
+        let end = range.end.min(self.len());
+        let start = range.start.min(end);
+        // Both positions are at most the length, one subtraction is enough
+        let absolute = |position: usize| {
+            let index = self.reader + position;
+            if index >= CAP {
+                index - CAP
+            } else {
+                index
+            }
+        };
+
+        RingBuffIter {
+            buffer: self,
+            index: absolute(start),
+            back: absolute(end),
+            remaining: end - start,
+        }
+    }
+
+    /// Returns an iterator on the elements starting from the
+    /// position `offset` and wrapping around to the oldest ones,
+    /// as if the buffer was rotated left, without moving anything.
+    /// The offset is taken modulo the length.
+    ///
+    /// # Arguments
+    /// * `offset` - Position of the first element yielded
+    ///
+    pub fn iter_rotated(&self, offset: usize) -> impl Iterator<Item = &T> {
+        let offset = offset.checked_rem(self.len()).unwrap_or(0);
+
+        self.range(offset..self.len()).chain(self.range(0..offset))
+    }
+
+    /// Returns a cursor on the oldest element, which can then
+    /// move over the buffer in both directions.
+    ///
+    /// # Arguments
+    ///
+    pub fn cursor(&self) -> Cursor<'_, T, CAP> {
+        Cursor {
+            buffer: self,
+            position: 0,
+        }
+    }
+
+    /// Returns an iterator on the buffer going
+    /// from the newest element to the oldest
+    ///
+    /// # Arguments
+    ///
+    pub fn iter_rev(&self) -> impl Iterator<Item = &T> {
+        let mut index = self.writer;
+
+        (0..self.len()).map(move |_| {
+            index = self.previous_index(index);
+            // SAFETY: the slots before the writer are live
+            unsafe { self.data[index].assume_init_ref() }
+        })
+    }
+
+    /// Returns an iterator on the elements from the oldest to the
+    /// newest, along with the index of their slot in the data array.
+    /// Meant to inspect how the elements wrap around.
+    ///
+    /// # Arguments
+    ///
+    pub fn iter_absolute(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(i, element)| (self.relative_to_absolute_index(i).expect("Index is valid."), element))
+    }
+
+    /// Returns an iterator on the elements from the oldest to the
+    /// newest, along with the index of their slot in the data array.
+    /// Same as `iter_absolute`.
+    ///
+    /// # Arguments
+    ///
+    pub fn iter_with_index(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter_absolute()
+    }
+
+    /// Returns an iterator on the `n` oldest elements,
+    /// from the oldest to the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements, clamped to the length
+    ///
+    pub fn first_n(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter().take(n)
+    }
+
+    /// Returns an iterator on the `n` newest elements,
+    /// from the oldest to the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements, clamped to the length
+    ///
+    pub fn last_n(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter().skip(self.len().saturating_sub(n))
+    }
+
+    /// Returns an iterator on the overlapping windows of `size`
+    /// consecutive elements, from the oldest window to the newest.
+    /// Yields nothing if `size` is 0 or greater than the length.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Number of elements in each window
+    ///
+    #[cfg(feature = "std")]
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = Vec<&T>> {
+        let count = if size == 0 || size > self.len() {
+            0
+        } else {
+            self.len() - size + 1
+        };
+
+        (0..count).map(move |start| {
+            (start..start + size)
+                .map(|i| self.get(i).expect("Index is valid."))
+                .collect()
+        })
+    }
+
+    /// Returns an iterator on the elements from the oldest to the
+    /// newest, along with their sequence id. Unlike the indexes of
+    /// `iter().enumerate()`, which start from 0 at the current front,
+    /// the id of an element does not change when older elements are
+    /// popped or overwritten: the id of the oldest element counts the
+    /// elements that left the front of the queue.
+    /// Ids are only unique among the current elements: the id of an
+    /// element dropped from the back, e.g. by `truncate`, is given to
+    /// the next element pushed.
+    /// Operations moving elements within the queue, like `rotate_left`,
+    /// `sort` or removing a middle element, renumber them by position.
+    ///
+    /// # Arguments
+    ///
+    pub fn iter_stable_enumerate(&self) -> impl Iterator<Item = (u64, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(i, element)| (self.front_id.wrapping_add(i as u64), element))
+    }
+
+    /// Returns an iterator on the consecutive groups of `size`
+    /// elements, from the oldest group to the newest. The last
+    /// group is shorter if the length is not a multiple of `size`.
+    /// Yields nothing if `size` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Number of elements in each group
+    ///
+    #[cfg(feature = "std")]
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Vec<&T>> {
+        let count = if size == 0 { 0 } else { self.len().div_ceil(size) };
+
+        (0..count).map(move |chunk| {
+            let start = chunk * size;
+            (start..self.len().min(start + size))
+                .map(|i| self.get(i).expect("Index is valid."))
+                .collect()
+        })
+    }
+
+    /// Returns an iterator on the elements fitting a predicate,
+    /// from the oldest to the newest.
+    /// Same as `iter().filter(pred)`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    pub fn iter_filter<'a, P>(&'a self, pred: P) -> impl Iterator<Item = &'a T>
+        where
+            P: FnMut(&&T) -> bool + 'a,
+    {
+        self.iter().filter(pred)
+    }
+
+    /// Returns an iterator yielding copies of the elements
+    /// from the oldest to the newest, read directly from the
+    /// two contiguous runs of the data array.
+    ///
+    /// # Arguments
+    ///
+    pub fn copied(&self) -> impl Iterator<Item = T> + '_
+        where
+            T: Copy,
+    {
+        let (first, second) = self.as_slices();
+
+        first.iter().chain(second).copied()
+    }
+
+    /// Returns a reference to the smallest element, or None
+    /// if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub fn min(&self) -> Option<&T>
+        where
+            T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Returns a reference to the largest element, or None
+    /// if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub fn max(&self) -> Option<&T>
+        where
+            T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Returns the sum of the elements.
+    ///
+    /// # Arguments
+    ///
+    /// # Examples
+    /// `let total: i32 = buffer.sum();`
+    ///
+    pub fn sum<'a, S>(&'a self) -> S
+        where
+            S: Sum<&'a T>,
+    {
+        self.iter().sum()
+    }
+
+    /// Folds the elements from the oldest to the newest into
+    /// an accumulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial value of the accumulator
+    /// * `f` - Combines the accumulator with an element
+    ///
+    /// # Examples
+    /// `let total = buffer.fold(0, |acc, x| acc + x);`
+    ///
+    pub fn fold<B, F>(&self, init: B, mut f: F) -> B
+        where
+            F: FnMut(B, &T) -> B,
+    {
+        let (first, second) = self.as_slices();
+
+        let acc = first.iter().fold(init, &mut f);
+        second.iter().fold(acc, f)
+    }
+
+    /// Folds the elements into a clone of the oldest one,
+    /// or returns None if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Combines the accumulator with an element
+    ///
+    pub fn reduce<F>(&self, f: F) -> Option<T>
+        where
+            T: Clone,
+            F: FnMut(T, &T) -> T,
+    {
+        let mut elements = self.iter();
+        let first = elements.next()?.clone();
+
+        Some(elements.fold(first, f))
+    }
+
+    /// Returns the sum of the elements, read directly from
+    /// the two contiguous runs of the data array.
+    ///
+    /// # Arguments
+    ///
+    pub fn sum_copied(&self) -> T
+        where
+            T: Copy + Sum,
+    {
+        let (first, second) = self.as_slices();
+
+        first.iter().chain(second).copied().sum()
+    }
+
+    /// Returns the smallest element, or None if the buffer is empty.
+    /// The elements are read directly from the two contiguous runs
+    /// of the data array.
+    ///
+    /// # Arguments
+    ///
+    pub fn min_copied(&self) -> Option<T>
+        where
+            T: Copy + Ord,
+    {
+        let (first, second) = self.as_slices();
+
+        // Each run is reduced on its own, which optimizes better than a chain
+        match (first.iter().min(), second.iter().min()) {
+            (Some(a), Some(b)) => Some(if b < a { *b } else { *a }),
+            (a, b) => a.or(b).copied(),
+        }
+    }
+
+    /// Returns the largest element, or None if the buffer is empty.
+    /// The elements are read directly from the two contiguous runs
+    /// of the data array.
+    ///
+    /// # Arguments
+    ///
+    pub fn max_copied(&self) -> Option<T>
+        where
+            T: Copy + Ord,
+    {
+        let (first, second) = self.as_slices();
+
+        // Each run is reduced on its own, which optimizes better than a chain
+        match (first.iter().max(), second.iter().max()) {
+            (Some(a), Some(b)) => Some(if b >= a { *b } else { *a }),
+            (a, b) => a.or(b).copied(),
+        }
+    }
+
+    /// Returns a new buffer holding the results of `f` applied to
+    /// each element, in the same order and with the same overflow
+    /// policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function applied to each element
+    ///
+    pub fn map<U, F>(&self, f: F) -> RingBuff<U, CAP>
+        where
+            F: FnMut(&T) -> U,
+    {
+        let mut mapped = RingBuff::with_policy(self.policy);
+
+        for element in self.iter().map(f) {
+            mapped.push_back(element);
+        }
+
+        mapped
+    }
+
+    /// Returns an iterator on the consecutive overlapping
+    /// pairs of elements, from the oldest pair to the newest.
+    ///
+    /// # Arguments
+    ///
+    pub fn pairwise(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.iter().zip(self.iter().skip(1))
+    }
+
+    /// Returns true if the buffer contains an element
+    /// equal to the given value.
+    ///
+    /// # Arguments
+    /// * `value` - The value to look for
+    ///
+    pub fn contains(&self, value: &T) -> bool
+        where
+            T: PartialEq,
+    {
+        self.iter().any(|element| element == value)
+    }
+
+    /// Returns true if the oldest elements are equal to `prefix`.
+    ///
+    /// # Arguments
+    /// * `prefix` - The elements to compare, from the oldest
+    ///
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+        where
+            T: PartialEq,
+    {
+        prefix.len() <= self.len() && self.iter().zip(prefix).all(|(a, b)| a == b)
+    }
+
+    /// Returns true if the newest elements are equal to `suffix`.
+    ///
+    /// # Arguments
+    /// * `suffix` - The elements to compare, ending with the newest
+    ///
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+        where
+            T: PartialEq,
+    {
+        suffix.len() <= self.len() && self.iter().skip(self.len() - suffix.len()).eq(suffix)
+    }
+
+    /// Returns the index of the first element fitting
+    /// a predicate, usable with `get`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    pub fn position<P>(&self, pred: P) -> Option<usize>
+        where
+            P: FnMut(&T) -> bool,
+    {
+        self.iter().position(pred)
+    }
+
+    /// Returns the index of the largest element, the first
+    /// one if several are equal, or None if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub fn position_max(&self) -> Option<usize>
+        where
+            T: Ord,
+    {
+        // On ties the lower index compares as the larger pair
+        self.iter()
+            .enumerate()
+            .max_by(|(i, a), (j, b)| a.cmp(b).then(j.cmp(i)))
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the index of the smallest element, the first
+    /// one if several are equal, or None if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub fn position_min(&self) -> Option<usize>
+        where
+            T: Ord,
+    {
+        self.iter()
+            .enumerate()
+            .min_by_key(|&(_, element)| element)
+            .map(|(index, _)| index)
+    }
+
+    /// Returns a reference to the first element fitting
+    /// a predicate.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    pub fn find<P>(&self, mut pred: P) -> Option<&T>
+        where
+            P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|element| pred(element))
+    }
+
+    /// Returns the number of elements fitting a predicate.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pred` - A predicate
+    ///
+    pub fn count_matching<P>(&self, mut pred: P) -> usize
+        where
+            P: FnMut(&T) -> bool,
+    {
+        self.iter().filter(|element| pred(element)).count()
+    }
+}
+
+impl<T, const CAP: usize> Drop for RingBuff<T, CAP> {
+    /// Drops the live elements, each exactly once.
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Debug, const CAP: usize> Debug for RingBuff<T, CAP> {
+    /// Lists the elements from the oldest to the newest,
+    /// the layout of the data array is left out.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display, const CAP: usize> Display for RingBuff<T, CAP> {
+    /// Prints the elements from the oldest to the newest,
+    /// as in `[100, 101, 102]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(element, f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<T: PartialEq, const CAP: usize> PartialEq for RingBuff<T, CAP> {
+    /// Two buffers are equal when they hold equal elements
+    /// in the same order, wherever they sit in the data array.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const CAP: usize> Eq for RingBuff<T, CAP> {}
+
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<[U]> for RingBuff<T, CAP> {
+    /// Compares the elements from the oldest to the newest
+    /// with the elements of the slice.
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for RingBuff<T, CAP> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<Vec<U>> for RingBuff<T, CAP> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U: PartialEq<T>, const CAP: usize> PartialEq<RingBuff<T, CAP>> for [U] {
+    fn eq(&self, other: &RingBuff<T, CAP>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U: PartialEq<T>, const CAP: usize, const N: usize> PartialEq<RingBuff<T, CAP>> for [U; N] {
+    fn eq(&self, other: &RingBuff<T, CAP>) -> bool {
+        self[..] == *other
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, U: PartialEq<T>, const CAP: usize> PartialEq<RingBuff<T, CAP>> for Vec<U> {
+    fn eq(&self, other: &RingBuff<T, CAP>) -> bool {
+        self[..] == *other
+    }
+}
+
+impl<T: Hash, const CAP: usize> Hash for RingBuff<T, CAP> {
+    /// Hashes the length and the elements in order, consistently
+    /// with `PartialEq`: the position of the elements in the data
+    /// array does not change the hash.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T, const CAP: usize> Default for RingBuff<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, const CAP: usize> From<Vec<T>> for RingBuff<T, CAP> {
+    /// Keeps the `CAP` last elements of the vector, in order,
+    /// and drops the other ones. They do not count as overwrites.
+    fn from(mut vec: Vec<T>) -> Self {
+        let mut buffer = Self::new();
+        vec.drain(..vec.len().saturating_sub(CAP));
+        for element in vec {
+            buffer.push_back(element);
+        }
+
+        buffer
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for RingBuff<T, CAP> {
+    /// Clones the elements into a new buffer, keeping the
+    /// overflow policy, the overwrite count and the element ids.
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_policy(self.policy);
+        for element in self.iter() {
+            clone.push_back(element.clone());
+        }
+        clone.overwrites = self.overwrites;
+        clone.front_id = self.front_id;
+
+        clone
+    }
+
+    /// Clones the elements of `source` over the existing ones
+    /// with `T::clone_from`, so that only the extra elements of
+    /// either buffer are dropped or cloned from scratch.
+    fn clone_from(&mut self, source: &Self) {
+        let (first, second) = self.as_mut_slices();
+        for (element, other) in first.iter_mut().chain(second).zip(source.iter()) {
+            element.clone_from(other);
+        }
+
+        self.truncate(source.len());
+        self.policy = source.policy;
+        for element in source.iter().skip(self.len()) {
+            self.push_back(element.clone());
+        }
+        self.overwrites = source.overwrites;
+        self.front_id = source.front_id;
+    }
+}
+
+/// Returns the smallest power of two greater or equal
+/// to the expected number of items, to be used as `CAP`.
+///
+/// # Arguments
+///
+/// * `expected_items` - The number of items the buffer should hold
+///
+/// # Examples
+/// `const CAP: usize = required_capacity(100); // 128`
+///
+pub const fn required_capacity(expected_items: usize) -> usize {
+    expected_items.next_power_of_two()
+}
+
+/// State of a retain pass, restoring the invariants of the
+/// buffer when dropped, including when the predicate panics
+struct RetainGuard<'a, T, const CAP: usize> {
+    /// The buffer being filtered
+    buffer: &'a mut RingBuff<T, CAP>,
+    /// The queue head once the pass is over, unused backward
+    reader: usize,
+    /// The boundary of the elements not checked yet
+    read: usize,
+    /// The boundary of the kept elements
+    write: usize,
+    /// Number of elements kept so far
+    kept: usize,
+    /// Number of elements not checked yet
+    remaining: usize,
+    /// True when the elements are checked from the newest one
+    backward: bool,
+}
+
+impl<T, const CAP: usize> Drop for RetainGuard<'_, T, CAP> {
+    /// Moves the elements not checked yet next to the kept ones,
+    /// then updates the positions of the buffer.
+    fn drop(&mut self) {
+        let mut read = self.read;
+        let mut write = self.write;
+
+        for _ in 0..self.remaining {
+            if self.backward {
+                read = self.buffer.previous_index(read);
+                write = self.buffer.previous_index(write);
+            }
+            if read != write {
+                // SAFETY: `read` was not checked yet and `write` was moved out
+                let element = unsafe { self.buffer.data[read].assume_init_read() };
+                self.buffer.data[write].write(element);
+            }
+            if !self.backward {
+                read = self.buffer.next_index(read);
+                write = self.buffer.next_index(write);
+            }
+        }
+
+        let reader = if self.backward { write } else { self.reader };
+        self.buffer.set_live_run(reader, self.kept + self.remaining);
+    }
+}
+
+/// Iterator returned by `extract_if`, yielding the removed elements
+pub struct ExtractIf<'a, T, P, const CAP: usize> {
+    /// The state of the pass, restoring the buffer when dropped
+    guard: RetainGuard<'a, T, CAP>,
+    /// True for the elements to remove
+    pred: P,
+}
+
+impl<T, P, const CAP: usize> Iterator for ExtractIf<'_, T, P, CAP>
+    where
+        P: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let guard = &mut self.guard;
+
+        while guard.remaining > 0 {
+            let read = guard.read;
+            // SAFETY: the slots from `read` were live before the pass
+            let extract = (self.pred)(unsafe { guard.buffer.data[read].assume_init_mut() });
+            guard.read = guard.buffer.next_index(read);
+            guard.remaining -= 1;
+
+            // SAFETY: `read` is live, it is moved out only once
+            let element = unsafe { guard.buffer.data[read].assume_init_read() };
+            if extract {
+                if guard.kept == 0 {
+                    guard.reader = guard.read;
+                    guard.write = guard.read;
+                    guard.buffer.front_id = guard.buffer.front_id.wrapping_add(1);
+                }
+                return Some(element);
+            }
+
+            guard.buffer.data[guard.write].write(element);
+            guard.write = guard.buffer.next_index(guard.write);
+            guard.kept += 1;
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.guard.remaining))
+    }
+}
+
+pub struct RingBuffIter<'a, T, const CAP: usize> {
+    /// A reference to the RingBuff
+    buffer: &'a RingBuff<T, CAP>,
+    /// The index of the next element from the front in the buffer data array
     index: usize,
-    /// Count of elements iterated through
-    count: usize,
+    /// The index following the next element from the back in the buffer data array
+    back: usize,
+    /// Count of elements not yielded yet from either end
+    remaining: usize,
+}
+
+impl<T, const CAP: usize> Clone for RingBuffIter<'_, T, CAP> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer,
+            index: self.index,
+            back: self.back,
+            remaining: self.remaining,
+        }
+    }
 }
 
 impl<'a, T, const CAP: usize> Iterator for RingBuffIter<'a, T, CAP> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.count == self.buffer.len() {
+        if self.remaining == 0 {
             None
         } else {
-            let current = &self.buffer.data[self.index];
+            // SAFETY: the slots between the two cursors are live
+            let current = unsafe { self.buffer.data[self.index].assume_init_ref() };
             self.index = self.buffer.next_index(self.index);
-            self.count += 1;
-            current.as_ref()
+            self.remaining -= 1;
+            Some(current)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    /// Skips `n` elements at once instead of one at a time.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.index = self.back;
+            self.remaining = 0;
+            return None;
+        }
+
+        self.index = (self.index + n) % CAP;
+        self.remaining -= n;
+        self.next()
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for RingBuffIter<'_, T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            None
+        } else {
+            self.back = self.buffer.previous_index(self.back);
+            self.remaining -= 1;
+            // SAFETY: the slots between the two cursors are live
+            Some(unsafe { self.buffer.data[self.back].assume_init_ref() })
+        }
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for RingBuffIter<'_, T, CAP> {}
+
+/// Position over the elements of a buffer which, unlike an
+/// iterator, can move back and forth
+pub struct Cursor<'a, T, const CAP: usize> {
+    /// A reference to the RingBuff
+    buffer: &'a RingBuff<T, CAP>,
+    /// Position of the current element, 0 being the oldest
+    position: usize,
+}
+
+impl<'a, T, const CAP: usize> Cursor<'a, T, CAP> {
+    /// Returns a reference to the current element,
+    /// or None if the buffer is empty.
+    ///
+    /// # Arguments
+    ///
+    pub fn current(&self) -> Option<&'a T> {
+        self.buffer.get(self.position)
+    }
+
+    /// Returns the position of the current element,
+    /// 0 being the oldest.
+    ///
+    /// # Arguments
+    ///
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves to the next newer element and returns true,
+    /// or returns false if the current one is the newest.
+    ///
+    /// # Arguments
+    ///
+    pub fn move_next(&mut self) -> bool {
+        if self.position + 1 < self.buffer.len() {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves to the next older element and returns true,
+    /// or returns false if the current one is the oldest.
+    ///
+    /// # Arguments
+    ///
+    pub fn move_prev(&mut self) -> bool {
+        if self.position > 0 {
+            self.position -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves to the element at a position and returns true,
+    /// or returns false and stays in place if it is out of bounds.
+    ///
+    /// # Arguments
+    /// * `position` - Position of the element, 0 being the oldest
+    ///
+    pub fn seek(&mut self, position: usize) -> bool {
+        if position < self.buffer.len() {
+            self.position = position;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves back to the oldest element.
+    ///
+    /// # Arguments
+    ///
+    pub fn rewind(&mut self) {
+        self.position = 0;
+    }
+}
+
+impl<T, const CAP: usize> Clone for Cursor<'_, T, CAP> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer,
+            position: self.position,
         }
     }
 }
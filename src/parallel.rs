@@ -0,0 +1,24 @@
+//! Rayon support, enabled by the `rayon` feature
+//!
+//! The two contiguous runs of the data array are split
+//! between the threads of the rayon pool.
+
+use rayon::prelude::*;
+
+use crate::RingBuff;
+
+impl<T: Sync, const CAP: usize> RingBuff<T, CAP> {
+    /// Returns a parallel iterator on the buffer, from the
+    /// oldest element to the newest.
+    ///
+    /// # Arguments
+    ///
+    /// # Examples
+    /// `let total: i64 = buffer.par_iter().sum();`
+    ///
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &T> {
+        let (first, second) = self.as_slices();
+
+        first.par_iter().chain(second.par_iter())
+    }
+}
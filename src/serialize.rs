@@ -0,0 +1,57 @@
+//! Serde support, enabled by the `serde` feature
+//!
+//! A buffer is serialized as the sequence of its elements, from the oldest
+//! to the newest, and deserialized by pushing them back in the same order.
+//! Deserializing a sequence longer than the capacity fails instead of
+//! dropping its oldest elements.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::RingBuff;
+
+impl<T: Serialize, const CAP: usize> Serialize for RingBuff<T, CAP> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self.iter() {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for RingBuff<T, CAP> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(RingBuffVisitor(PhantomData))
+    }
+}
+
+/// Rebuilds a buffer from a sequence of elements
+struct RingBuffVisitor<T, const CAP: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const CAP: usize> Visitor<'de> for RingBuffVisitor<T, CAP> {
+    type Value = RingBuff<T, CAP>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of at most {} elements", CAP)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut buffer = RingBuff::new();
+        while let Some(element) = seq.next_element()? {
+            if buffer.is_full() {
+                // Count the remaining elements to report the actual length
+                let mut len = CAP + 1;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    len += 1;
+                }
+                return Err(A::Error::invalid_length(len, &self));
+            }
+            buffer.push_back(element);
+        }
+        Ok(buffer)
+    }
+}
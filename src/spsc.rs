@@ -0,0 +1,302 @@
+//! Single producer single consumer channel, enabled by the `std` feature
+//!
+//! `channel` splits a ring buffer into a `Producer`, pushing to the
+//! back of the queue, and a `Consumer`, popping from its front, which
+//! can be moved to two different threads. They are synchronized with
+//! atomics on the queue head and tail. With `OverflowPolicy::Reject`
+//! neither end ever waits for the other, with `OverflowPolicy::Overwrite`
+//! the producer of a full queue spins until a pop in progress is over.
+//!
+//! The data array holds one more slot than the capacity, so that the
+//! producer never writes to the slot the consumer is moving out of.
+//! The lowest bit of the stored head tells whether the consumer is
+//! in the middle of a pop.
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug};
+use core::hint;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{wrap, OverflowPolicy};
+
+/// Set in the stored head while the consumer moves an element out
+const BUSY: usize = 1;
+
+/// State shared by the two ends of a channel
+struct Shared<T, const CAP: usize> {
+    /// `CAP + 1` slots, the live ones hold the elements of the queue
+    data: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// The queue head, shifted left by one, with the `BUSY` bit
+    reader: AtomicUsize,
+    /// The queue tail, only stored by the producer
+    writer: AtomicUsize,
+    /// Behavior of `push` when the queue is full
+    policy: OverflowPolicy,
+}
+
+// SAFETY: an element is only accessed by the end owning its slot,
+// the ownership of a slot being handed over through `reader` and `writer`
+unsafe impl<T: Send, const CAP: usize> Sync for Shared<T, CAP> {}
+
+impl<T, const CAP: usize> Shared<T, CAP> {
+    /// Returns the index of the next slot in data.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The original index
+    ///
+    fn next_index(index: usize) -> usize {
+        wrap::next(index, CAP + 1)
+    }
+
+    /// Returns the number of elements between a head and a tail.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The index of the queue head
+    /// * `writer` - The index of the queue tail
+    ///
+    fn distance(reader: usize, writer: usize) -> usize {
+        (writer + CAP + 1 - reader) % (CAP + 1)
+    }
+
+    /// Returns the number of elements in the queue.
+    /// The other end may change it right after it is read.
+    ///
+    /// # Arguments
+    ///
+    fn len(&self) -> usize {
+        let reader = self.reader.load(Ordering::Acquire) >> 1;
+        let writer = self.writer.load(Ordering::Acquire);
+        Self::distance(reader, writer)
+    }
+}
+
+impl<T, const CAP: usize> Drop for Shared<T, CAP> {
+    fn drop(&mut self) {
+        let mut reader = *self.reader.get_mut() >> 1;
+        let writer = *self.writer.get_mut();
+
+        while reader != writer {
+            // SAFETY: both ends are gone, the slots from the head
+            // to the tail hold the remaining elements
+            unsafe { self.data[reader].get_mut().assume_init_drop() };
+            reader = Self::next_index(reader);
+        }
+    }
+}
+
+/// Sending end of a channel, pushes to the back of the queue
+pub struct Producer<T, const CAP: usize> {
+    shared: Arc<Shared<T, CAP>>,
+}
+
+/// Receiving end of a channel, pops from the front of the queue
+pub struct Consumer<T, const CAP: usize> {
+    shared: Arc<Shared<T, CAP>>,
+}
+
+/// Returns the two ends of a channel holding up to `CAP` elements,
+/// overwriting the oldest element when full.
+/// Overwriting waits for the consumer to finish moving an element
+/// out of the queue, so a consumer suspended in the middle of `pop`
+/// blocks the producer of a full queue until it resumes.
+/// Use `channel_with_policy` with `OverflowPolicy::Reject` for a
+/// producer that never waits.
+///
+/// # Arguments
+///
+/// # Examples
+/// `let (producer, consumer) = spsc::channel::<i32, 64>();`
+///
+pub fn channel<T, const CAP: usize>() -> (Producer<T, CAP>, Consumer<T, CAP>) {
+    channel_with_policy(OverflowPolicy::default())
+}
+
+/// Returns the two ends of a channel holding up to `CAP` elements,
+/// applying the given policy when pushing into a full queue.
+///
+/// # Arguments
+///
+/// * `policy` - The overflow policy
+///
+/// # Examples
+/// `let (producer, consumer) = spsc::channel_with_policy::<i32, 64>(OverflowPolicy::Reject);`
+///
+pub fn channel_with_policy<T, const CAP: usize>(policy: OverflowPolicy) -> (Producer<T, CAP>, Consumer<T, CAP>) {
+    let shared = Arc::new(Shared {
+        data: (0..=CAP).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        reader: AtomicUsize::new(0),
+        writer: AtomicUsize::new(0),
+        policy,
+    });
+
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+impl<T, const CAP: usize> Producer<T, CAP> {
+    /// Pushes one element to the back of the queue.
+    /// When the queue is full, the oldest element is dropped with
+    /// `OverflowPolicy::Overwrite`, with `OverflowPolicy::Reject`
+    /// the element is given back.
+    /// To overwrite, the producer may wait for the consumer to
+    /// finish moving an element out of the queue.
+    ///
+    /// # Arguments
+    /// * `element` - The element to add to the queue
+    ///
+    /// # Errors
+    /// Returns the element if the queue is full and the policy
+    /// is `OverflowPolicy::Reject`.
+    ///
+    pub fn push(&mut self, element: T) -> Result<(), T> {
+        let shared = &*self.shared;
+
+        if CAP == 0 {
+            return match shared.policy {
+                OverflowPolicy::Overwrite => Ok(()),
+                OverflowPolicy::Reject => Err(element),
+            };
+        }
+
+        let writer = shared.writer.load(Ordering::Relaxed);
+
+        loop {
+            let head = shared.reader.load(Ordering::Acquire);
+            let reader = head >> 1;
+            if Shared::<T, CAP>::distance(reader, writer) < CAP {
+                // With a pop in progress, the consumer's slot is the one
+                // before the head, which the tail cannot reach here
+                break;
+            }
+
+            match shared.policy {
+                OverflowPolicy::Reject => return Err(element),
+                OverflowPolicy::Overwrite if head & BUSY != 0 => hint::spin_loop(),
+                OverflowPolicy::Overwrite => {
+                    let next = Shared::<T, CAP>::next_index(reader) << 1;
+                    if shared
+                        .reader
+                        .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        // SAFETY: moving the head past the oldest element
+                        // gave its slot back to the producer
+                        unsafe { (*shared.data[reader].get()).assume_init_drop() };
+                        break;
+                    }
+                }
+            }
+        }
+
+        // SAFETY: the slot at the tail is neither live nor being moved out
+        unsafe { (*shared.data[writer].get()).write(element) };
+        shared
+            .writer
+            .store(Shared::<T, CAP>::next_index(writer), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// # Arguments
+    ///
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Returns true if the queue contains no elements.
+    ///
+    /// # Arguments
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of elements the
+    /// queue can hold.
+    ///
+    /// # Arguments
+    ///
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Consumer<T, CAP> {
+    /// Remove one element from the front of the queue
+    /// and returns it.
+    ///
+    /// # Arguments
+    ///
+    pub fn pop(&mut self) -> Option<T> {
+        let shared = &*self.shared;
+
+        loop {
+            let head = shared.reader.load(Ordering::Acquire);
+            let reader = head >> 1;
+            if reader == shared.writer.load(Ordering::Acquire) {
+                return None;
+            }
+
+            let next = Shared::<T, CAP>::next_index(reader) << 1 | BUSY;
+            // Fails if the producer overwrote the oldest element in between
+            if shared
+                .reader
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                // SAFETY: the slot was live, the producer does not write
+                // to it while the `BUSY` bit is set
+                let element = unsafe { (*shared.data[reader].get()).assume_init_read() };
+                shared.reader.fetch_and(!BUSY, Ordering::Release);
+                return Some(element);
+            }
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// # Arguments
+    ///
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Returns true if the queue contains no elements.
+    ///
+    /// # Arguments
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of elements the
+    /// queue can hold.
+    ///
+    /// # Arguments
+    ///
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Debug for Producer<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer").field("len", &self.len()).finish()
+    }
+}
+
+impl<T, const CAP: usize> Debug for Consumer<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer").field("len", &self.len()).finish()
+    }
+}
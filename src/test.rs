@@ -1,7 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use crate::RingBuff;
-    use super::*;
+    use std::cell::Cell;
+
+    use crate::{required_capacity, spsc, CapacityError, Checkpoint, DynRingBuff, OverflowPolicy, RingBuff};
+
+    /// Counts how many times it is dropped
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
 
     #[test]
     fn can_construct_ring_buffer() {
@@ -237,11 +247,11 @@ mod tests {
         buffer.clear();
         let mut empty = true;
 
-        for val in buffer.data.iter() {
-            empty = val.is_none();
+        for i in 0..10 {
+            empty = buffer.slot(i).is_none();
         }
 
-        assert_eq!(empty, true);
+        assert!(empty);
     }
 
     #[test]
@@ -310,7 +320,7 @@ mod tests {
             *val = 40
         }
 
-        assert_eq!(buffer.data[2], Some(40));
+        assert_eq!(buffer.slot(2), Some(&40));
     }
 
     #[test]
@@ -334,5 +344,3191 @@ mod tests {
         let i4 = buffer.relative_to_absolute_index(3);
 
         assert_eq!((i1, i2, i3, i4), (Some(2), Some(3), Some(4), Some(0)));
+        assert_eq!(buffer.relative_to_absolute_index(4), None);
+
+        // Every reader position of a full buffer, across the wrap boundary
+        let mut full: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..5 {
+            full.push_back(i);
+        }
+        for reader in 0..5 {
+            assert_eq!(full.reader, reader);
+            for index in 0..5 {
+                assert_eq!(full.relative_to_absolute_index(index), Some((reader + index) % 5));
+            }
+            assert_eq!(full.relative_to_absolute_index(5), None);
+            full.push_back(5);
+        }
+    }
+
+    #[test]
+    fn contains_present_value() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+
+        assert!(buffer.contains(&101));
+    }
+
+    #[test]
+    fn contains_absent_value() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        assert!(!buffer.contains(&102));
+    }
+
+    #[test]
+    fn contains_overwritten_value() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Overwrite oldest (100)
+        buffer.push_back(104);
+
+        assert!(!buffer.contains(&100));
+        assert!(buffer.contains(&104));
+    }
+
+    #[test]
+    fn peek_nth_counts_from_oldest() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        assert_eq!(buffer.peek_nth(0), Some(&102));
+        assert_eq!(buffer.peek_nth(3), Some(&105));
+        assert_eq!(buffer.peek_nth(4), None);
+    }
+
+    #[test]
+    fn peek_nth_back_counts_from_newest() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        assert_eq!(buffer.peek_nth_back(0), Some(&105));
+        assert_eq!(buffer.peek_nth_back(3), Some(&102));
+        assert_eq!(buffer.peek_nth_back(4), None);
+    }
+
+    #[test]
+    fn position_round_trips_through_get() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        let index = buffer.position(|x| *x == 104).unwrap();
+
+        assert_eq!(index, 2);
+        assert_eq!(buffer.get(index), Some(&104));
+        assert_eq!(buffer.position(|x| *x == 100), None);
+    }
+
+    #[test]
+    fn find_in_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+
+        assert_eq!(buffer.find(|x| *x > 102), Some(&103));
+        assert_eq!(buffer.find(|x| *x > 200), None);
+    }
+
+    #[test]
+    fn from_parts_valid_wrapped() {
+        let data = [Some(104), None, Some(102), Some(103)];
+        let buffer: RingBuff<i32, 4> = RingBuff::from_parts(data, 2, 1, 3).unwrap();
+
+        let mut result = [0, 0, 0];
+
+        for (i, val) in buffer.iter().enumerate() {
+            result[i] = *val;
+        }
+
+        assert_eq!([102, 103, 104], result);
+    }
+
+    #[test]
+    fn from_parts_valid_full_and_empty() {
+        let full: RingBuff<i32, 3> = RingBuff::from_parts([Some(1), Some(2), Some(3)], 1, 1, 3).unwrap();
+        let empty: RingBuff<i32, 3> = RingBuff::from_parts([None, None, None], 2, 2, 0).unwrap();
+
+        assert_eq!(full.get(0), Some(&2));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn from_parts_rejects_inconsistent_parts() {
+        // Size exceeds capacity
+        assert!(RingBuff::<i32, 2>::from_parts([Some(1), Some(2)], 0, 0, 3).is_err());
+        // Reader out of range
+        assert!(RingBuff::<i32, 2>::from_parts([None, None], 2, 0, 0).is_err());
+        // Writer not matching reader and size
+        assert!(RingBuff::<i32, 3>::from_parts([Some(1), Some(2), None], 0, 0, 2).is_err());
+        // Hole in the live run
+        assert!(RingBuff::<i32, 3>::from_parts([Some(1), None, None], 0, 2, 2).is_err());
+        // Element outside the live run
+        assert!(RingBuff::<i32, 3>::from_parts([Some(1), None, Some(3)], 0, 1, 1).is_err());
+    }
+
+    #[test]
+    fn iter_rev_full_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        let mut forward = [0, 0, 0, 0];
+        let mut backward = [0, 0, 0, 0];
+
+        for (i, val) in buffer.iter().enumerate() {
+            forward[i] = *val;
+        }
+        for (i, val) in buffer.iter_rev().enumerate() {
+            backward[i] = *val;
+        }
+        forward.reverse();
+
+        assert_eq!(forward, backward);
+        assert_eq!([105, 104, 103, 102], backward);
+    }
+
+    #[test]
+    fn iter_rev_partially_filled_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(103);
+        buffer.push_back(104);
+
+        let mut forward = [0, 0, 0];
+        let mut backward = [0, 0, 0];
+
+        for (i, val) in buffer.iter().enumerate() {
+            forward[i] = *val;
+        }
+        for (i, val) in buffer.iter_rev().enumerate() {
+            backward[i] = *val;
+        }
+        forward.reverse();
+
+        assert_eq!(forward, backward);
+        assert_eq!([104, 103, 102], backward);
+    }
+
+    #[test]
+    fn into_parts_from_parts_round_trip() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(103);
+        buffer.push_back(104);
+
+        let (data, reader, writer, size) = buffer.into_parts();
+        let rebuilt: RingBuff<i32, 4> = RingBuff::from_parts(data, reader, writer, size).unwrap();
+
+        let slots: Vec<Option<i32>> = (0..4).map(|i| rebuilt.slot(i).copied()).collect();
+        assert_eq!(slots, data.to_vec());
+        assert_eq!((rebuilt.reader, rebuilt.writer, rebuilt.len()), (1, 1, 4));
+
+        let mut result = [0, 0, 0, 0];
+
+        for (i, val) in rebuilt.iter().enumerate() {
+            result[i] = *val;
+        }
+
+        assert_eq!([101, 102, 103, 104], result);
+    }
+
+    #[test]
+    fn clear_drops_each_element_once() {
+        let drops = Cell::new(0);
+        let mut buffer: RingBuff<DropCounter, 4> = RingBuff::new();
+        buffer.push_back(DropCounter(&drops));
+        buffer.push_back(DropCounter(&drops));
+        buffer.push_back(DropCounter(&drops));
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(DropCounter(&drops));
+        buffer.push_back(DropCounter(&drops));
+        assert_eq!(drops.get(), 1);
+
+        buffer.clear();
+
+        assert_eq!(drops.get(), 5);
+        assert!(buffer.is_empty());
+        assert!((0..4).all(|i| buffer.slot(i).is_none()));
+        assert_eq!((buffer.reader, buffer.writer), (0, 0));
+    }
+
+    #[test]
+    fn retain_mut_into_collects_removed_in_order() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        buffer.push_back(1);
+        buffer.push_back(2);
+        buffer.push_back(3);
+        buffer.push_back(4);
+        buffer.push_back(5);
+        buffer.push_back(6);
+
+        // Wrap around
+        buffer.push_back(7);
+        buffer.push_back(8);
+
+        let mut removed = Vec::new();
+        buffer.retain_mut_into(|x| *x % 2 == 0, &mut removed);
+
+        let survivors: Vec<i32> = buffer.iter().copied().collect();
+
+        assert_eq!(survivors, vec![4, 6, 8]);
+        assert_eq!(removed, vec![3, 5, 7]);
+        assert_eq!(buffer.len(), 3);
+
+        buffer.push_back(10);
+        assert_eq!(buffer.get(3), Some(&10));
+    }
+
+    #[test]
+    fn truncate_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        buffer.truncate(10);
+        assert_eq!(buffer.len(), 4);
+
+        buffer.truncate(3);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104]);
+
+        buffer.truncate(1);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102]);
+
+        buffer.push_back(106);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 106]);
+
+        buffer.truncate(0);
+        assert!(buffer.is_empty());
+        assert!((0..4).all(|i| buffer.slot(i).is_none()));
+    }
+
+    #[test]
+    fn retain_count_aligned() {
+        let mut buffer: RingBuff<i32, 10> = RingBuff::new();
+        buffer.push_back(10);
+        buffer.push_back(101);
+        buffer.push_back(12);
+        buffer.push_back(51);
+
+        let len = buffer.len();
+        let removed = buffer.retain_count(|x| *x < 50);
+
+        assert_eq!(removed, 2);
+        assert_eq!(removed, len - buffer.len());
+        assert_eq!(buffer.retain_count(|x| *x < 50), 0);
+    }
+
+    #[test]
+    fn retain_count_unaligned() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(10);
+        buffer.push_back(101);
+        buffer.push_back(12);
+        buffer.push_back(13);
+        buffer.push_back(51);
+
+        // Wrap around
+        buffer.push_back(351);
+        buffer.push_back(25);
+
+        let len = buffer.len();
+        let removed = buffer.retain_count(|x| *x < 50);
+
+        assert_eq!(removed, 2);
+        assert_eq!(removed, len - buffer.len());
+    }
+
+    #[test]
+    fn required_capacity_is_next_power_of_two() {
+        const CAP: usize = required_capacity(100);
+        let buffer: RingBuff<i32, CAP> = RingBuff::new();
+
+        assert_eq!(required_capacity(100), 128);
+        assert_eq!(required_capacity(128), 128);
+        assert_eq!(required_capacity(1), 1);
+        assert_eq!(buffer.capacity(), 128);
+    }
+
+    #[test]
+    fn retain_mut_large_buffer() {
+        const CAP: usize = 10_000;
+        let mut buffer: RingBuff<usize, CAP> = RingBuff::new();
+
+        for i in 0..CAP + CAP / 2 {
+            buffer.push_back(i);
+        }
+
+        buffer.retain_mut(|x| *x % 3 == 0);
+
+        let expected = (CAP / 2..CAP + CAP / 2).filter(|x| x % 3 == 0).count();
+
+        assert_eq!(buffer.len(), expected);
+        // The removed front run only advanced the reader
+        assert_eq!(buffer.get(0), Some(&(CAP / 2 + 1)));
+        assert_eq!(buffer.reader, CAP / 2 + 1);
+        assert!(buffer.iter().zip(buffer.iter().skip(1)).all(|(a, b)| a + 3 == *b));
+    }
+
+    #[test]
+    fn drain_while_front_returns_leading_run() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(50);
+        buffer.push_back(50);
+        buffer.push_back(1);
+        buffer.push_back(2);
+        buffer.push_back(3);
+
+        // Wrap around
+        buffer.push_back(40);
+        buffer.push_back(4);
+
+        let drained = buffer.drain_while_front(|x| *x < 10);
+        let rest: Vec<i32> = buffer.iter().copied().collect();
+
+        assert_eq!(drained, vec![1, 2, 3]);
+        assert_eq!(rest, vec![40, 4]);
+        assert!(buffer.drain_while_front(|x| *x < 10).is_empty());
+    }
+
+    #[test]
+    fn drain_while_front_empties_buffer() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        buffer.push_back(1);
+        buffer.push_back(2);
+
+        let drained = buffer.drain_while_front(|_| true);
+
+        assert_eq!(drained, vec![1, 2]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn rotate_left_full_buffer() {
+        let mut buffer: RingBuff<char, 4> = RingBuff::new();
+        buffer.push_back('a');
+        buffer.push_back('b');
+        buffer.push_back('c');
+        buffer.push_back('d');
+
+        buffer.rotate_left(2);
+        let result: Vec<char> = buffer.iter().copied().collect();
+
+        assert_eq!(result, vec!['c', 'd', 'a', 'b']);
+    }
+
+    #[test]
+    fn rotate_left_after_wrap() {
+        let mut buffer: RingBuff<char, 5> = RingBuff::new();
+        buffer.push_back('x');
+        buffer.push_back('x');
+        buffer.push_back('x');
+        buffer.pop();
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back('a');
+        buffer.push_back('b');
+        buffer.push_back('c');
+        buffer.push_back('d');
+
+        buffer.rotate_left(6);
+        let result: Vec<char> = buffer.iter().copied().collect();
+
+        assert_eq!(result, vec!['c', 'd', 'a', 'b']);
+    }
+
+    #[test]
+    fn rotate_right_undoes_rotate_left() {
+        let mut buffer: RingBuff<char, 5> = RingBuff::new();
+        buffer.push_back('x');
+        buffer.push_back('x');
+        buffer.push_back('x');
+        buffer.pop();
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back('a');
+        buffer.push_back('b');
+        buffer.push_back('c');
+        buffer.push_back('d');
+
+        buffer.rotate_right(1);
+        let result: Vec<char> = buffer.iter().copied().collect();
+        assert_eq!(result, vec!['d', 'a', 'b', 'c']);
+
+        buffer.rotate_left(1);
+        let result: Vec<char> = buffer.iter().copied().collect();
+        assert_eq!(result, vec!['a', 'b', 'c', 'd']);
+
+        buffer.push_back('e');
+        buffer.rotate_right(2);
+        let result: Vec<char> = buffer.iter().copied().collect();
+        assert_eq!(result, vec!['d', 'e', 'a', 'b', 'c']);
+    }
+
+    #[test]
+    fn cloned_iterator_resumes_from_checkpoint() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+
+        let mut iter = buffer.iter();
+        iter.next();
+        let checkpoint = iter.clone();
+
+        let rest: Vec<i32> = iter.copied().collect();
+        let resumed: Vec<i32> = checkpoint.copied().collect();
+
+        assert_eq!(rest, vec![102, 103, 104]);
+        assert_eq!(rest, resumed);
+    }
+
+    #[test]
+    fn reverse_full_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+        buffer.push_back(104);
+
+        // Wrap around
+        buffer.push_back(105);
+        buffer.push_back(106);
+
+        buffer.reverse();
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![106, 105, 104, 103, 102]);
+
+        buffer.push_back(107);
+        assert_eq!(buffer.get(0), Some(&105));
+        assert_eq!(buffer.get(4), Some(&107));
+    }
+
+    #[test]
+    fn reverse_empty_and_single_element() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        buffer.reverse();
+        assert!(buffer.is_empty());
+
+        buffer.push_back(100);
+        buffer.reverse();
+        assert_eq!(buffer.get(0), Some(&100));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn get_pair_mut_across_wrap() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+
+        if let Some((a, b)) = buffer.get_pair_mut(0) {
+            let mean = (*a + *b) / 2;
+            *a = mean;
+            *b = mean;
+        }
+        if let Some((a, b)) = buffer.get_pair_mut(2) {
+            *a += 1;
+            *b += 1;
+        }
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+
+        assert_eq!(result, vec![101, 101, 104, 105]);
+        assert!(buffer.get_pair_mut(3).is_none());
+    }
+
+    #[test]
+    fn swap_elements_in_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+
+        buffer.swap(0, 3);
+
+        assert_eq!(buffer.get(0), Some(&104));
+        assert_eq!(buffer.get(3), Some(&101));
+        assert_eq!(buffer.get(1), Some(&102));
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_out_of_bounds_panics() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        buffer.swap(0, 2);
+    }
+
+    #[test]
+    fn fill_cycling_repeats_pattern() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        buffer.fill_cycling([1, 2, 3]);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+
+        assert_eq!(result, vec![1, 2, 3, 1, 2, 3, 1, 2]);
+        assert_eq!(buffer.len(), 8);
+    }
+
+    #[test]
+    fn fill_cycling_empty_pattern() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::new();
+        buffer.push_back(100);
+
+        buffer.fill_cycling(Vec::new());
+
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn sort_scrambled_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.pop();
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(42);
+        buffer.push_back(7);
+        buffer.push_back(19);
+        buffer.push_back(-3);
+        buffer.push_back(7);
+
+        buffer.sort();
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![-3, 7, 7, 19, 42]);
+
+        buffer.push_back(1);
+        assert_eq!(buffer.get(5), Some(&1));
+        assert_eq!(buffer.len(), 6);
+    }
+
+    #[test]
+    fn sort_by_key_wrapped_buffer() {
+        let mut buffer: RingBuff<(char, i32), 4> = RingBuff::new();
+        buffer.push_back(('a', 4));
+        buffer.push_back(('b', 3));
+        buffer.push_back(('c', 2));
+        buffer.push_back(('d', 1));
+
+        // Wrap around
+        buffer.push_back(('e', 0));
+
+        buffer.sort_by_key(|&(_, key)| key);
+        let result: Vec<char> = buffer.iter().map(|&(name, _)| name).collect();
+        assert_eq!(result, vec!['e', 'd', 'c', 'b']);
+
+        buffer.sort_by(|a, b| b.cmp(a));
+        let result: Vec<char> = buffer.iter().map(|&(name, _)| name).collect();
+        assert_eq!(result, vec!['e', 'd', 'c', 'b']);
+    }
+
+    #[test]
+    fn retain_front_run_only_advances_reader() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.pop();
+        buffer.pop();
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(1);
+        buffer.push_back(2);
+        buffer.push_back(3);
+        buffer.push_back(50);
+        buffer.push_back(51);
+
+        let before: Vec<Option<i32>> = (0..6).map(|i| buffer.slot(i).copied()).collect();
+        let reader = buffer.reader;
+        let writer = buffer.writer;
+
+        buffer.retain(|x| *x >= 10);
+
+        assert_eq!(buffer.reader, (reader + 3) % 6);
+        assert_eq!(buffer.writer, writer);
+        assert_eq!(buffer.len(), 2);
+        for i in 0..buffer.len() {
+            let index = buffer.relative_to_absolute_index(i).unwrap();
+            assert_eq!(buffer.slot(index).copied(), before[index]);
+        }
+    }
+
+    #[test]
+    fn binary_search_sorted_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(0);
+        buffer.push_back(0);
+        buffer.push_back(0);
+
+        // Wrap around
+        buffer.push_back(10);
+        buffer.push_back(20);
+        buffer.push_back(30);
+        buffer.push_back(40);
+        buffer.push_back(50);
+
+        assert_eq!(buffer.binary_search(&10), Ok(0));
+        assert_eq!(buffer.binary_search(&40), Ok(3));
+        assert_eq!(buffer.binary_search(&50), Ok(4));
+        assert_eq!(buffer.binary_search(&5), Err(0));
+        assert_eq!(buffer.binary_search(&35), Err(3));
+        assert_eq!(buffer.binary_search(&60), Err(5));
+    }
+
+    #[test]
+    fn try_push_front_prepends() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(101);
+        buffer.push_back(102);
+
+        assert_eq!(buffer.try_push_front(100), Ok(()));
+        assert_eq!(buffer.try_push_front(99), Ok(()));
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![99, 100, 101, 102]);
+        assert_eq!(buffer.pop(), Some(99));
+    }
+
+    #[test]
+    fn try_push_front_rejects_when_full() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        assert_eq!(buffer.try_push_front(99), Err(99));
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101]);
+    }
+
+    #[test]
+    fn try_push_back_when_room() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::new();
+
+        assert_eq!(buffer.try_push_back(100), Ok(()));
+        assert_eq!(buffer.try_push_back(101), Ok(()));
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn try_push_back_rejects_when_full() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+
+        assert_eq!(buffer.try_push_back(103), Err(103));
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101, 102]);
+        assert_eq!(buffer.pop(), Some(100));
+    }
+
+    #[test]
+    fn segment_lengths_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        assert_eq!(buffer.segment_lengths(), (3, 0));
+
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(103);
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        let (first, second) = buffer.segment_lengths();
+
+        assert_eq!((first, second), (3, 1));
+        assert_eq!(first + second, buffer.len());
+    }
+
+    #[test]
+    fn default_policy_overwrites() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::with_policy(OverflowPolicy::default());
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![101, 102, 103]);
+    }
+
+    #[test]
+    fn overwrite_policy_drops_oldest() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::with_policy(OverflowPolicy::Overwrite);
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![101, 102, 103]);
+    }
+
+    #[test]
+    fn reject_policy_drops_incoming() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::with_policy(OverflowPolicy::Reject);
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101, 102]);
+
+        buffer.pop();
+        buffer.push_back(104);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![101, 102, 104]);
+    }
+
+    #[test]
+    fn retain_mut_indexed_keeps_even_positions() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+        buffer.push_back(104);
+
+        // Wrap around
+        buffer.push_back(105);
+        buffer.push_back(106);
+
+        buffer.retain_mut_indexed(|i, x| {
+            *x += 1000;
+            i % 2 == 0
+        });
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![1102, 1104, 1106]);
+    }
+
+    #[test]
+    fn push_back_with_evicted_returns_oldest_on_overwrite() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+
+        assert_eq!(buffer.push_back_with_evicted(100), None);
+        assert_eq!(buffer.push_back_with_evicted(101), None);
+        assert_eq!(buffer.push_back_with_evicted(102), None);
+        assert_eq!(buffer.push_back_with_evicted(103), Some(100));
+        assert_eq!(buffer.push_back_with_evicted(104), Some(101));
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104]);
+    }
+
+    #[test]
+    fn push_back_with_evicted_returns_rejected_element() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::with_policy(OverflowPolicy::Reject);
+
+        assert_eq!(buffer.push_back_with_evicted(100), None);
+        assert_eq!(buffer.push_back_with_evicted(101), None);
+        assert_eq!(buffer.push_back_with_evicted(102), Some(102));
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101]);
+    }
+
+    #[test]
+    fn extend_with_evicted_streams_evictions() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+
+        let evicted: Vec<i32> = buffer.extend_with_evicted([103, 104, 105, 106]).collect();
+        assert_eq!(evicted, vec![100, 101, 102]);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![103, 104, 105, 106]);
+    }
+
+    #[test]
+    fn extend_with_evicted_is_lazy() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+
+        let mut evictions = buffer.extend_with_evicted([100, 101]);
+        assert_eq!(evictions.next(), None);
+        drop(evictions);
+
+        assert_eq!(buffer.len(), 2);
+
+        drop(buffer.extend_with_evicted([102, 103]));
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn overwrite_count_matches_evictions() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+
+        for i in 0..4 {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.overwrite_count(), 0);
+
+        for i in 4..14 {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.overwrite_count(), 10);
+
+        buffer.pop();
+        buffer.push_back(14);
+        assert_eq!(buffer.overwrite_count(), 10);
+
+        buffer.reset_overwrite_count();
+        assert_eq!(buffer.overwrite_count(), 0);
+
+        buffer.push_back(15);
+        assert_eq!(buffer.overwrite_count(), 1);
+    }
+
+    #[test]
+    fn rejected_pushes_are_not_overwrites() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::with_policy(OverflowPolicy::Reject);
+
+        for i in 0..10 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.overwrite_count(), 0);
+    }
+
+    #[test]
+    fn restore_rewinds_consumption() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+
+        let checkpoint: Checkpoint = buffer.checkpoint();
+
+        buffer.advance(3);
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.get(0), Some(&104));
+
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![101, 102, 103, 104]);
+    }
+
+    #[test]
+    fn advance_clamps_to_length() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        buffer.advance(10);
+        assert!(buffer.is_empty());
+
+        buffer.push_back(102);
+        assert_eq!(buffer.get(0), Some(&102));
+    }
+
+    #[test]
+    fn advance_wraps_around() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+
+        buffer.advance(3);
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.get(0), Some(&105));
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((5, &105)));
+
+        buffer.push_back(106);
+        buffer.push_back(107);
+        buffer.push_back(108);
+        buffer.advance(4);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.reader, buffer.writer);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn restore_after_overwrite_panics_in_debug() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.push_back(102);
+
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+    }
+
+    #[test]
+    #[should_panic(expected = "Elements were pushed since the checkpoint.")]
+    #[cfg(debug_assertions)]
+    fn restore_after_push_panics_in_debug() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(1);
+        buffer.push_back(2);
+        buffer.push_back(3);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.advance(2);
+        buffer.push_back(4);
+        buffer.push_back(5);
+
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+    }
+
+    #[test]
+    #[should_panic(expected = "The checkpoint does not belong to this buffer.")]
+    fn restore_checkpoint_of_larger_buffer_panics() {
+        let mut large: RingBuff<i32, 64> = RingBuff::new();
+        for i in 0..40 {
+            large.push_back(i);
+        }
+        large.advance(40);
+        let checkpoint = large.checkpoint();
+
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        // SAFETY: the checkpoint is rejected before any slot is read
+        unsafe { buffer.restore(checkpoint) };
+    }
+
+    #[test]
+    fn restore_after_try_push_front() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.try_push_front(100).unwrap();
+
+        let checkpoint = buffer.checkpoint();
+        buffer.advance(1);
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100]);
+    }
+
+    #[test]
+    fn restore_after_from_parts() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::from_parts([None, None, Some(100), Some(101)], 2, 0, 2).unwrap();
+
+        let checkpoint = buffer.checkpoint();
+        buffer.advance(2);
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101]);
+    }
+
+    #[test]
+    fn restore_after_wrapped_rotate() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.try_push_front(101).unwrap();
+        buffer.try_push_front(100).unwrap();
+        buffer.push_back(102);
+
+        // The oldest element moves to a slot that was never written
+        buffer.rotate_left(1);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.advance(3);
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![101, 102, 100]);
+    }
+
+    #[test]
+    fn pairwise_first_differences() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(0);
+        buffer.push_back(1);
+        buffer.push_back(3);
+        buffer.push_back(6);
+        buffer.push_back(10);
+
+        // Wrap around
+        buffer.push_back(15);
+
+        let differences: Vec<i32> = buffer.pairwise().map(|(a, b)| b - a).collect();
+
+        assert_eq!(differences, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn pairwise_needs_two_elements() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        assert_eq!(buffer.pairwise().count(), 0);
+
+        buffer.push_back(0);
+        assert_eq!(buffer.pairwise().count(), 0);
+    }
+
+    #[test]
+    fn equal_buffers_with_different_offsets() {
+        let mut a: RingBuff<i32, 4> = RingBuff::new();
+        a.push_back(101);
+        a.push_back(102);
+
+        let mut b: RingBuff<i32, 4> = RingBuff::new();
+        b.push_back(100);
+        b.push_back(100);
+        b.push_back(101);
+        b.push_back(102);
+        b.pop();
+        b.pop();
+
+        assert_eq!(a, b);
+
+        b.push_back(103);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_round_trip_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+
+        // Wrap around
+        buffer.push_back(104);
+        buffer.push_back(105);
+
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, "[102,103,104,105]");
+
+        let restored: RingBuff<i32, 4> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, buffer);
+        assert_eq!((restored.reader, restored.writer, restored.len()), (0, 0, 4));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_deserialize_partial_buffer() {
+        let restored: RingBuff<i32, 4> = serde_json::from_str("[1,2]").unwrap();
+
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.writer, 2);
+        assert_eq!(restored.get(1), Some(&2));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_deserialize_rejects_longer_sequence() {
+        let result: Result<RingBuff<i32, 4>, _> = serde_json::from_str("[1,2,3,4,5,6]");
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("invalid length 6, expected a sequence of at most 4 elements"), "{}", error);
+
+        let larger: RingBuff<i32, 8> = serde_json::from_str("[1,2,3,4,5,6]").unwrap();
+        let json = serde_json::to_string(&larger).unwrap();
+        assert!(serde_json::from_str::<RingBuff<i32, 4>>(&json).is_err());
+    }
+
+    #[test]
+    fn len_after_push_caps_at_capacity() {
+        let mut buffer: RingBuff<i32, 2> = RingBuff::new();
+        assert_eq!(buffer.len_after_push(), 1);
+
+        buffer.push_back(100);
+        assert_eq!(buffer.len_after_push(), 2);
+
+        buffer.push_back(101);
+        assert_eq!(buffer.len_after_push(), 2);
+
+        buffer.push_back(102);
+        assert_eq!(buffer.len_after_push(), 2);
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn write_keeps_last_bytes() {
+        use std::io::Write;
+
+        let mut buffer: RingBuff<u8, 4> = RingBuff::new();
+
+        let written = buffer.write(b"abcdefg").unwrap();
+        let result: Vec<u8> = buffer.iter().copied().collect();
+
+        assert_eq!(written, 7);
+        assert_eq!(result, b"defg".to_vec());
+    }
+
+    #[test]
+    fn write_macro_into_buffer() {
+        use std::io::Write;
+
+        let mut buffer: RingBuff<u8, 8> = RingBuff::new();
+        write!(buffer, "x={}", 42).unwrap();
+        buffer.flush().unwrap();
+
+        let result: Vec<u8> = buffer.iter().copied().collect();
+        assert_eq!(result, b"x=42".to_vec());
+    }
+
+    #[test]
+    fn write_with_reject_policy_stops_when_full() {
+        use std::io::Write;
+
+        let mut buffer: RingBuff<u8, 4> = RingBuff::with_policy(OverflowPolicy::Reject);
+
+        assert_eq!(buffer.write(b"abcdefg").unwrap(), 4);
+        assert_eq!(buffer.write(b"h").unwrap(), 0);
+
+        let result: Vec<u8> = buffer.iter().copied().collect();
+        assert_eq!(result, b"abcd".to_vec());
+    }
+
+    #[test]
+    fn rotate_contiguous_fast_path_matches_moving_path() {
+        let mut fast: RingBuff<i32, 8> = RingBuff::new();
+        let mut moving: RingBuff<i32, 8> = RingBuff::new();
+        for i in 0..6 {
+            fast.push_back(i);
+            moving.push_back(i);
+        }
+        fast.pop();
+        moving.pop();
+
+        fast.rotate_left(2);
+        moving.rotate_left_moving(2);
+        assert_eq!(fast, moving);
+        // Rotated in place, the indices did not move
+        assert_eq!((fast.reader, fast.writer), (1, 6));
+
+        fast.rotate_right(3);
+        moving.rotate_right_moving(3);
+        assert_eq!(fast, moving);
+        assert_eq!((fast.reader, fast.writer), (1, 6));
+
+        let result: Vec<i32> = fast.iter().copied().collect();
+        assert_eq!(result, vec![5, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rotate_wrapped_buffer_moves_elements() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.push_back(103);
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(104);
+
+        buffer.rotate_left(1);
+
+        // Not contiguous, the indices moved
+        assert_eq!((buffer.reader, buffer.writer), (3, 2));
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![103, 104, 102]);
+    }
+
+    #[test]
+    fn read_pops_oldest_bytes_first() {
+        use std::io::{Read, Write};
+
+        let mut buffer: RingBuff<u8, 8> = RingBuff::new();
+        buffer.write_all(b"hello").unwrap();
+
+        let mut first = [0; 3];
+        assert_eq!(buffer.read(&mut first).unwrap(), 3);
+        assert_eq!(&first, b"hel");
+        assert_eq!(buffer.len(), 2);
+
+        let mut rest = [0; 8];
+        assert_eq!(buffer.read(&mut rest).unwrap(), 2);
+        assert_eq!(&rest[..2], b"lo");
+
+        assert_eq!(buffer.read(&mut rest).unwrap(), 0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn read_across_wrapped_buffer() {
+        use std::io::{Read, Write};
+
+        let mut buffer: RingBuff<u8, 4> = RingBuff::new();
+        buffer.write_all(b"abcdef").unwrap();
+
+        let mut result = Vec::new();
+        buffer.read_to_end(&mut result).unwrap();
+
+        assert_eq!(result, b"cdef".to_vec());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn copied_sums_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 1..=6 {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.segment_lengths(), (2, 2));
+
+        let result: Vec<i32> = buffer.copied().collect();
+        assert_eq!(result, vec![3, 4, 5, 6]);
+        assert_eq!(buffer.copied().sum::<i32>(), 18);
+
+        let empty: RingBuff<i32, 4> = RingBuff::new();
+        assert_eq!(empty.copied().count(), 0);
+    }
+
+    #[test]
+    fn elements_are_dropped_exactly_once() {
+        use std::rc::Rc;
+
+        let handles: Vec<Rc<i32>> = (0..10).map(Rc::new).collect();
+        let counts = |handles: &[Rc<i32>]| handles.iter().map(Rc::strong_count).collect::<Vec<usize>>();
+
+        let mut buffer: RingBuff<Rc<i32>, 4> = RingBuff::new();
+        for handle in &handles {
+            buffer.push_back(Rc::clone(handle));
+        }
+        assert_eq!(counts(&handles), vec![1, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+
+        drop(buffer.pop());
+        buffer.retain(|x| **x != 8);
+        assert_eq!(counts(&handles), vec![1, 1, 1, 1, 1, 1, 1, 2, 1, 2]);
+
+        // Wrap around, then move the elements around
+        buffer.push_back(Rc::clone(&handles[0]));
+        buffer.push_back(Rc::clone(&handles[1]));
+        buffer.rotate_left(1);
+        buffer.sort();
+        buffer.reverse();
+        buffer.truncate(3);
+        let result: Vec<i32> = buffer.iter().map(|x| **x).collect();
+        assert_eq!(result, vec![9, 7, 1]);
+        assert_eq!(counts(&handles), vec![1, 2, 1, 1, 1, 1, 1, 2, 1, 2]);
+
+        drop(buffer);
+        assert_eq!(counts(&handles), vec![1; 10]);
+    }
+
+    #[test]
+    fn drop_partially_full_and_wrapped_buffers() {
+        let drops = Cell::new(0);
+        let mut buffer: RingBuff<DropCounter, 4> = RingBuff::new();
+        buffer.push_back(DropCounter(&drops));
+        buffer.push_back(DropCounter(&drops));
+        drop(buffer);
+        assert_eq!(drops.get(), 2);
+
+        let drops = Cell::new(0);
+        let mut buffer: RingBuff<DropCounter, 4> = RingBuff::new();
+        for _ in 0..6 {
+            buffer.push_back(DropCounter(&drops));
+        }
+        drop(buffer.pop());
+        assert_eq!(drops.get(), 3);
+        drop(buffer);
+        assert_eq!(drops.get(), 6);
+
+        // The elements moved out by into_parts are not dropped with the buffer
+        let drops = Cell::new(0);
+        let mut buffer: RingBuff<DropCounter, 2> = RingBuff::new();
+        buffer.push_back(DropCounter(&drops));
+        let (data, ..) = buffer.into_parts();
+        assert_eq!(drops.get(), 0);
+        drop(data);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn debug_lists_live_elements() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.pop();
+
+        let debug = format!("{:?}", buffer);
+        assert_eq!(debug, "[101]");
+        assert!(!debug.contains("None"));
+
+        for i in 102..105 {
+            buffer.push_back(i);
+        }
+        assert_eq!(format!("{:?}", buffer), "[102, 103, 104]");
+        assert_eq!(format!("{:#?}", buffer), "[\n    102,\n    103,\n    104,\n]");
+    }
+
+    #[test]
+    fn remove_shifts_shorter_side() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.remove(1), Some(3));
+        assert_eq!(buffer.remove(3), Some(6));
+        assert_eq!(buffer.remove(4), None);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![2, 4, 5, 7]);
+
+        buffer.push_back(8);
+        buffer.push_back(9);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![2, 4, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn remove_first_matching_from_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in [100, 101, 102, 103, 101, 104, 105] {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.segment_lengths(), (3, 2));
+
+        assert_eq!(buffer.remove_first_matching(&101), Some(101));
+        assert_eq!(buffer.remove_first_matching(&101), None);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104, 105]);
+        assert_eq!(buffer.len(), 4);
+    }
+
+    #[test]
+    fn drop_releases_rc_elements() {
+        use std::rc::Rc;
+
+        let value = Rc::new(String::from("element"));
+
+        // Partially full
+        let mut buffer: RingBuff<Rc<String>, 4> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for _ in 0..3 {
+            buffer.push_back(Rc::clone(&value));
+        }
+        assert_eq!(Rc::strong_count(&value), 4);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        // Wrapped, with elements rejected and removed on the way
+        let mut buffer: RingBuff<Rc<String>, 4> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for _ in 0..4 {
+            buffer.push_back(Rc::clone(&value));
+        }
+        buffer.push_back(Rc::clone(&value));
+        buffer.pop();
+        buffer.pop();
+        buffer.push_back(Rc::clone(&value));
+        buffer.remove(0);
+        assert_eq!(buffer.segment_lengths(), (1, 1));
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        // Rebuilt from parts
+        let data = [Some(Rc::clone(&value)), None, Some(Rc::clone(&value))];
+        let buffer: RingBuff<Rc<String>, 3> = RingBuff::from_parts(data, 2, 1, 2).unwrap();
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn iter_filter_keeps_positive_values() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in [3, -1, 4, -1, -5, 9, 2] {
+            buffer.push_back(i);
+        }
+
+        let result: Vec<i32> = buffer.iter_filter(|x| **x > 0).copied().collect();
+        assert_eq!(result, vec![4, 9, 2]);
+
+        let result: Vec<i32> = buffer.iter_filter(|x| **x < 0).copied().collect();
+        assert_eq!(result, vec![-1, -5]);
+    }
+
+    #[test]
+    fn hash_ignores_offset() {
+        use std::collections::HashSet;
+
+        let mut contiguous: RingBuff<i32, 4> = RingBuff::new();
+        contiguous.push_back(101);
+        contiguous.push_back(102);
+        contiguous.push_back(103);
+
+        let mut wrapped: RingBuff<i32, 4> = RingBuff::new();
+        for i in 99..104 {
+            wrapped.push_back(i);
+        }
+        wrapped.pop();
+        assert_ne!(wrapped.reader, contiguous.reader);
+
+        let mut set = HashSet::new();
+        set.insert(contiguous);
+        set.insert(wrapped);
+        assert_eq!(set.len(), 1);
+
+        let mut other: RingBuff<i32, 4> = RingBuff::new();
+        other.push_back(101);
+        set.insert(other);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn retain_rev_keeps_order_of_survivors() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in [9, 9, 1, 2, 3, 4, 5, 6, 7, 8] {
+            buffer.push_back(i);
+        }
+
+        // Keep the two most recent even values
+        let mut visited = Vec::new();
+        let mut remaining = 2;
+        buffer.retain_rev(|x| {
+            visited.push(*x);
+            let keep = *x % 2 == 0 && remaining > 0;
+            remaining -= keep as usize;
+            keep
+        });
+
+        assert_eq!(visited, vec![8, 7, 6, 5, 4, 3]);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![6, 8]);
+
+        buffer.push_back(10);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![6, 8, 10]);
+
+        buffer.retain_rev(|_| false);
+        assert!(buffer.is_empty());
+        buffer.push_back(11);
+        assert_eq!(buffer.get(0), Some(&11));
+    }
+
+    #[test]
+    fn display_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        assert_eq!(buffer.to_string(), "[]");
+
+        for i in 99..103 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.to_string(), "[100, 101, 102]");
+        assert_eq!(format!("{:>4}", buffer), "[ 100,  101,  102]");
+    }
+
+    #[test]
+    fn try_into_capacity_fitting_elements() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for i in 0..10 {
+            buffer.push_back(i);
+        }
+        for _ in 0..5 {
+            buffer.pop();
+        }
+        buffer.push_back(10);
+
+        let small: RingBuff<i32, 4> = buffer.try_into_capacity().unwrap();
+        let result: Vec<i32> = small.iter().copied().collect();
+        assert_eq!(result, vec![5, 6, 7, 10]);
+        assert_eq!(small.capacity(), 4);
+        assert_eq!(small.policy, OverflowPolicy::Reject);
+    }
+
+    #[test]
+    fn try_into_capacity_rejects_too_many_elements() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::new();
+        for i in 0..5 {
+            buffer.push_back(i);
+        }
+
+        let buffer = buffer.try_into_capacity::<4>().unwrap_err();
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn iter_absolute_follows_wrap_around() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+
+        let result: Vec<(usize, i32)> = buffer.iter_absolute().map(|(index, x)| (index, *x)).collect();
+        assert_eq!(result, vec![(3, 103), (0, 104), (1, 105)]);
+    }
+
+    #[test]
+    fn first_n_and_last_n_clamp_to_length() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+
+        let first: Vec<i32> = buffer.first_n(2).copied().collect();
+        let last: Vec<i32> = buffer.last_n(2).copied().collect();
+        assert_eq!(first, vec![103, 104]);
+        assert_eq!(last, vec![104, 105]);
+
+        let first: Vec<i32> = buffer.first_n(10).copied().collect();
+        let last: Vec<i32> = buffer.last_n(10).copied().collect();
+        assert_eq!(first, vec![103, 104, 105]);
+        assert_eq!(last, vec![103, 104, 105]);
+
+        assert_eq!(buffer.first_n(0).count(), 0);
+        assert_eq!(buffer.last_n(0).count(), 0);
+    }
+
+    #[test]
+    fn try_retain_stops_at_first_error() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 1..=7 {
+            buffer.push_back(i);
+        }
+
+        let mut calls = 0;
+        let result = buffer.try_retain(|x| {
+            calls += 1;
+            if calls == 3 {
+                Err("third element")
+            } else {
+                Ok(*x % 2 == 0)
+            }
+        });
+
+        assert_eq!(result, Err("third element"));
+        assert_eq!(calls, 3);
+        assert_eq!(buffer.validate(), Ok(()));
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![4, 5, 6, 7]);
+
+        assert_eq!(buffer.try_retain(|x| Ok::<bool, ()>(*x > 5)), Ok(()));
+        assert_eq!(buffer.validate(), Ok(()));
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![6, 7]);
+    }
+
+    #[test]
+    fn windows_over_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+
+        let windows: Vec<Vec<i32>> = buffer.windows(3).map(|w| w.into_iter().copied().collect()).collect();
+        assert_eq!(windows, vec![vec![102, 103, 104], vec![103, 104, 105]]);
+
+        assert_eq!(buffer.windows(1).count(), 4);
+        assert_eq!(buffer.windows(4).count(), 1);
+        assert_eq!(buffer.windows(5).count(), 0);
+        assert_eq!(buffer.windows(0).count(), 0);
+    }
+
+    #[test]
+    fn enumerate_indexes_are_relative_to_front() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..104 {
+            buffer.push_back(i);
+        }
+
+        let before: Vec<(usize, i32)> = buffer.iter().enumerate().map(|(i, x)| (i, *x)).collect();
+        assert_eq!(before[1], (1, 101));
+
+        buffer.pop();
+        let after: Vec<(usize, i32)> = buffer.iter().enumerate().map(|(i, x)| (i, *x)).collect();
+        assert_eq!(after[0], (0, 101));
+
+        buffer.rotate_left(1);
+        let rotated: Vec<(usize, i32)> = buffer.iter().enumerate().map(|(i, x)| (i, *x)).collect();
+        assert_eq!(rotated, vec![(0, 102), (1, 103), (2, 101)]);
+    }
+
+    #[test]
+    fn stable_ids_persist_across_pops() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..104 {
+            buffer.push_back(i);
+        }
+
+        buffer.pop();
+        buffer.pop();
+        let result: Vec<(u64, i32)> = buffer.iter_stable_enumerate().map(|(id, x)| (id, *x)).collect();
+        assert_eq!(result, vec![(2, 102), (3, 103)]);
+
+        // Overwrites also move the front id
+        for i in 104..107 {
+            buffer.push_back(i);
+        }
+        let result: Vec<(u64, i32)> = buffer.iter_stable_enumerate().map(|(id, x)| (id, *x)).collect();
+        assert_eq!(result, vec![(3, 103), (4, 104), (5, 105), (6, 106)]);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.advance(2);
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((5, &105)));
+
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((3, &103)));
+
+        buffer.clear();
+        buffer.push_back(107);
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((7, &107)));
+    }
+
+    #[test]
+    fn removing_the_front_moves_the_stable_id() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        assert_eq!(buffer.remove(0), Some(100));
+
+        buffer.push_back(101);
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((1, &101)));
+
+        buffer.push_back(102);
+        buffer.push_back(103);
+        assert_eq!(buffer.remove(0), Some(101));
+        let result: Vec<(u64, i32)> = buffer.iter_stable_enumerate().map(|(id, x)| (id, *x)).collect();
+        assert_eq!(result, vec![(2, 102), (3, 103)]);
+    }
+
+    #[test]
+    fn truncated_ids_are_given_again() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        buffer.truncate(0);
+        buffer.push_back(102);
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((0, &102)));
+    }
+
+    #[test]
+    fn chunks_evenly_divisible() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in 0..9 {
+            buffer.push_back(i);
+        }
+
+        let chunks: Vec<Vec<i32>> = buffer.chunks(2).map(|c| c.into_iter().copied().collect()).collect();
+        assert_eq!(chunks, vec![vec![3, 4], vec![5, 6], vec![7, 8]]);
+        assert_eq!(buffer.chunks(0).count(), 0);
+    }
+
+    #[test]
+    fn chunks_with_remainder() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+
+        let chunks: Vec<Vec<i32>> = buffer.chunks(2).map(|c| c.into_iter().copied().collect()).collect();
+        assert_eq!(chunks, vec![vec![3, 4], vec![5, 6], vec![7]]);
+
+        let chunks: Vec<Vec<i32>> = buffer.chunks(10).map(|c| c.into_iter().copied().collect()).collect();
+        assert_eq!(chunks, vec![vec![3, 4, 5, 6, 7]]);
+    }
+
+    #[test]
+    fn try_swap_in_bounds() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+
+        assert!(buffer.try_swap(0, 3));
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![105, 103, 104, 102]);
+
+        assert!(buffer.try_swap(1, 1));
+        assert_eq!(buffer.get(1), Some(&103));
+    }
+
+    #[test]
+    fn try_swap_out_of_bounds_leaves_buffer_unchanged() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+        buffer.push_back(101);
+
+        assert!(!buffer.try_swap(0, 2));
+        assert!(!buffer.try_swap(5, 1));
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![100, 101]);
+    }
+
+    #[test]
+    fn dyn_push_pop_with_runtime_capacity() {
+        let capacity = "3".parse().unwrap();
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(capacity);
+        assert_eq!(buffer.capacity(), 3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+
+        buffer.push_back(100);
+        buffer.push_back(101);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.pop(), Some(100));
+        assert_eq!(buffer.get(0), Some(&101));
+        assert_eq!(buffer.get(1), None);
+    }
+
+    #[test]
+    fn dyn_overflow_overwrites_oldest() {
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(4);
+        for i in 100..107 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.len(), 4);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![103, 104, 105, 106]);
+        assert_eq!(format!("{:?}", buffer), "[103, 104, 105, 106]");
+    }
+
+    #[test]
+    fn dyn_wrap_around_after_pops() {
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(4);
+        buffer.push_back(100);
+        buffer.push_back(101);
+        buffer.push_back(102);
+        buffer.pop();
+        buffer.pop();
+
+        // Wrap around
+        buffer.push_back(103);
+        buffer.push_back(104);
+        buffer.push_back(105);
+        if let Some(x) = buffer.get_mut(3) {
+            *x = 50;
+        }
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104, 50]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        buffer.push_back(106);
+        assert_eq!(buffer.get(0), Some(&106));
+    }
+
+    #[test]
+    fn dyn_zero_capacity_drops_pushes() {
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(0);
+        buffer.push_back(100);
+
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+        assert_eq!(buffer.iter().count(), 0);
+    }
+
+    #[test]
+    fn retain_mask_keeps_alternating_elements() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in 0..9 {
+            buffer.push_back(i);
+        }
+
+        let keep: Vec<bool> = (0..buffer.len()).map(|i| i % 2 == 1).collect();
+        buffer.retain_mask(&keep);
+
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![4, 6, 8]);
+        assert_eq!(buffer.validate(), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Mask length does not match buffer length.")]
+    fn retain_mask_wrong_length_panics() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+
+        buffer.retain_mask(&[true, false]);
+    }
+
+    #[test]
+    fn dyn_resize_grows_wrapped_buffer() {
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(3);
+        for i in 100..105 {
+            buffer.push_back(i);
+        }
+
+        buffer.resize(5);
+        assert_eq!(buffer.capacity(), 5);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104]);
+
+        buffer.push_back(105);
+        buffer.push_back(106);
+        buffer.push_back(107);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![103, 104, 105, 106, 107]);
+    }
+
+    #[test]
+    fn dyn_resize_shrinks_keeping_newest() {
+        let mut buffer: DynRingBuff<i32> = DynRingBuff::with_capacity(5);
+        for i in 100..107 {
+            buffer.push_back(i);
+        }
+
+        buffer.resize(2);
+        assert_eq!(buffer.capacity(), 2);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![105, 106]);
+
+        buffer.push_back(107);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![106, 107]);
+
+        buffer.resize(0);
+        assert!(buffer.is_empty());
+        buffer.resize(1);
+        buffer.push_back(108);
+        assert_eq!(buffer.get(0), Some(&108));
+    }
+
+    #[test]
+    fn append_full_buffer_into_half_full_one() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(1);
+        buffer.push_back(2);
+
+        let mut other: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            other.push_back(i);
+        }
+
+        buffer.append(&mut other);
+
+        assert!(other.is_empty());
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![102, 103, 104, 105]);
+        assert_eq!(buffer.overwrite_count(), 2);
+
+        // The emptied buffer can still be used
+        other.push_back(106);
+        buffer.append(&mut other);
+        let result: Vec<i32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![103, 104, 105, 106]);
+    }
+
+    #[test]
+    fn copied_aggregates_match_iter() {
+        let mut buffer: RingBuff<i64, 6> = RingBuff::new();
+        assert_eq!(buffer.sum_copied(), 0);
+        assert_eq!(buffer.min_copied(), None);
+        assert_eq!(buffer.max_copied(), None);
+
+        for i in [5, -3, 8, 2, -7, 4, 9, 1] {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.segment_lengths(), (4, 2));
+
+        assert_eq!(buffer.sum_copied(), buffer.iter().sum::<i64>());
+        assert_eq!(buffer.min_copied(), buffer.iter().min().copied());
+        assert_eq!(buffer.max_copied(), buffer.iter().max().copied());
+        assert_eq!((buffer.min_copied(), buffer.max_copied()), (Some(-7), Some(9)));
+    }
+
+    #[test]
+    fn mutators_stay_consistent_after_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let wrapped = || {
+            let mut buffer: RingBuff<Rc<i32>, 6> = RingBuff::new();
+            for i in -1..=6 {
+                buffer.push_back(Rc::new(i));
+            }
+            buffer
+        };
+        let values = |buffer: &RingBuff<Rc<i32>, 6>| buffer.iter().map(|x| **x).collect::<Vec<i32>>();
+
+        let mut buffer = wrapped();
+        let kept = buffer.get(1).cloned().unwrap();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            buffer.retain(|x| {
+                assert_ne!(**x, 3);
+                **x % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(buffer.validate(), Ok(()));
+        assert_eq!(values(&buffer), vec![2, 3, 4, 5, 6]);
+        assert_eq!(Rc::strong_count(&kept), 2);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&kept), 1);
+
+        let mut buffer = wrapped();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            buffer.retain_rev(|x| {
+                assert_ne!(**x, 4);
+                **x % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(buffer.validate(), Ok(()));
+        assert_eq!(values(&buffer), vec![1, 2, 3, 4, 6]);
+
+        let mut buffer = wrapped();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            buffer.drain_while_front(|x| {
+                assert_ne!(**x, 3);
+                true
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(buffer.validate(), Ok(()));
+        assert_eq!(values(&buffer), vec![3, 4, 5, 6]);
+
+        let mut buffer = wrapped();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            buffer.sort_by(|a, b| {
+                assert!(**a != 5 && **b != 5);
+                b.cmp(a)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(buffer.validate(), Ok(()));
+        assert_eq!(buffer.len(), 6);
+
+        // The buffer is still usable, every element is dropped once
+        buffer.pop();
+        buffer.push_back(Rc::clone(&kept));
+        buffer.retain(|x| Rc::ptr_eq(x, &kept));
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(Rc::strong_count(&kept), 2);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&kept), 1);
+    }
+
+    #[test]
+    fn split_off_wrapped_buffer() {
+        let wrapped = || {
+            let mut buffer: RingBuff<i32, 5> = RingBuff::with_policy(OverflowPolicy::Reject);
+            for i in 100..105 {
+                buffer.push_back(i);
+            }
+            buffer.pop();
+            buffer.pop();
+            buffer.push_back(105);
+            buffer.push_back(106);
+            buffer
+        };
+        let values = |buffer: &RingBuff<i32, 5>| buffer.iter().copied().collect::<Vec<i32>>();
+
+        let mut front = wrapped();
+        let back = front.split_off(0);
+        assert!(front.is_empty());
+        assert_eq!(values(&back), vec![102, 103, 104, 105, 106]);
+        assert_eq!(back.policy, OverflowPolicy::Reject);
+
+        let mut front = wrapped();
+        let back = front.split_off(2);
+        assert_eq!(values(&front), vec![102, 103]);
+        assert_eq!(values(&back), vec![104, 105, 106]);
+        front.push_back(107);
+        assert_eq!(values(&front), vec![102, 103, 107]);
+
+        let mut front = wrapped();
+        let back = front.split_off(5);
+        assert_eq!(values(&front), vec![102, 103, 104, 105, 106]);
+        assert!(back.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Split index out of bounds.")]
+    fn split_off_past_the_end_panics() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        buffer.push_back(100);
+
+        buffer.split_off(2);
+    }
+
+    #[test]
+    fn segments_write_wrapped_bytes() {
+        use std::io::Write;
+
+        let mut buffer: RingBuff<u8, 8> = RingBuff::new();
+        assert_eq!(buffer.segments().count(), 0);
+
+        buffer.write_all(b"0123456789ab").unwrap();
+        assert_eq!(buffer.segments().count(), 2);
+
+        let mut out = Vec::new();
+        for segment in buffer.segments() {
+            out.write_all(segment).unwrap();
+        }
+        assert_eq!(out, b"456789ab".to_vec());
+
+        let logical: Vec<u8> = buffer.iter().copied().collect();
+        assert_eq!(out, logical);
+
+        buffer.truncate(3);
+        assert_eq!(buffer.segments().collect::<Vec<&[u8]>>(), vec![b"456"]);
+    }
+
+    #[test]
+    fn as_mut_slices_cover_live_elements() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..6 {
+            buffer.push_back(i);
+        }
+
+        let (first, second) = buffer.as_mut_slices();
+        assert_eq!((&*first, &*second), (&[2, 3][..], &[4, 5][..]));
+        first[0] = 20;
+        second[1] = 50;
+
+        assert_eq!(buffer.as_slices(), (&[20, 3][..], &[4, 50][..]));
+    }
+
+    #[test]
+    fn min_max_sum_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        assert_eq!(buffer.min(), None);
+        assert_eq!(buffer.max(), None);
+        assert_eq!(buffer.sum::<i32>(), 0);
+
+        for i in [50, 7, -2, 13, 4, 9, -6] {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.min(), Some(&-6));
+        assert_eq!(buffer.max(), Some(&13));
+        assert_eq!(buffer.sum::<i32>(), 18);
+    }
+
+    #[test]
+    fn iter_mixing_both_ends() {
+        // Every fill level and offset of a small buffer
+        for len in 0..=5 {
+            for offset in 0..5 {
+                let mut buffer: RingBuff<usize, 5> = RingBuff::new();
+                for i in 0..offset {
+                    buffer.push_back(i);
+                }
+                for _ in 0..offset {
+                    buffer.pop();
+                }
+                for i in 0..len {
+                    buffer.push_back(i);
+                }
+
+                // Patterns of next (true) and next_back (false)
+                for pattern in 0..(1 << len) {
+                    let mut iter = buffer.iter();
+                    let mut front = Vec::new();
+                    let mut back = Vec::new();
+
+                    for step in 0..len {
+                        assert_eq!(iter.len(), len - step);
+                        if pattern & (1 << step) != 0 {
+                            front.push(*iter.next().unwrap());
+                        } else {
+                            back.push(*iter.next_back().unwrap());
+                        }
+                    }
+                    assert_eq!(iter.next(), None);
+                    assert_eq!(iter.next_back(), None);
+
+                    back.reverse();
+                    front.extend(back);
+                    assert_eq!(front, (0..len).collect::<Vec<usize>>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iter_rev_through_double_ended_iterator() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+
+        let backward: Vec<i32> = buffer.iter().rev().copied().collect();
+        assert_eq!(backward, vec![105, 104, 103, 102]);
+        assert_eq!(buffer.iter().rev().collect::<Vec<&i32>>(), buffer.iter_rev().collect::<Vec<&i32>>());
+
+        let mut iter = buffer.iter();
+        assert_eq!(iter.next_back(), Some(&105));
+        let rest: Vec<i32> = iter.copied().collect();
+        assert_eq!(rest, vec![102, 103, 104]);
+    }
+
+    #[test]
+    fn fill_makes_buffer_full() {
+        let mut buffer: RingBuff<String, 4> = RingBuff::new();
+        buffer.push_back(String::from("a"));
+        buffer.push_back(String::from("b"));
+        buffer.pop();
+
+        buffer.fill(String::from("baseline"));
+
+        assert_eq!(buffer.len(), buffer.capacity());
+        assert!(buffer.iter().all(|x| x == "baseline"));
+        assert_eq!((buffer.reader, buffer.writer), (0, 0));
+    }
+
+    #[test]
+    fn fill_with_incrementing_counter() {
+        let mut buffer: RingBuff<u32, 5> = RingBuff::new();
+        for i in 0..7 {
+            buffer.push_back(i);
+        }
+
+        let mut counter = 0;
+        buffer.fill_with(|| {
+            counter += 1;
+            counter * 10
+        });
+
+        let result: Vec<u32> = buffer.iter().copied().collect();
+        assert_eq!(result, vec![10, 20, 30, 40, 50]);
+        assert_eq!((buffer.reader, buffer.writer), (0, 0));
+    }
+
+    #[test]
+    fn map_wrapped_buffer_to_strings() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 100..106 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+
+        let mapped: RingBuff<String, 4> = buffer.map(|x| format!("#{}", x));
+
+        assert_eq!(mapped.len(), buffer.len());
+        let result: Vec<&str> = mapped.iter().map(String::as_str).collect();
+        assert_eq!(result, vec!["#103", "#104", "#105"]);
+    }
+
+    #[test]
+    fn iter_with_index_matches_absolute_indexes() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 100..108 {
+            buffer.push_back(i);
+        }
+
+        for (i, (index, element)) in buffer.iter_with_index().enumerate() {
+            assert_eq!(Some(index), buffer.relative_to_absolute_index(i));
+            assert_eq!(Some(element), buffer.get(i));
+        }
+        assert_eq!(buffer.iter_with_index().map(|(index, _)| index).collect::<Vec<usize>>(), vec![3, 4, 0, 1, 2]);
+    }
+
+    #[test]
+    fn zero_capacity_push_and_pop() {
+        let mut buffer: RingBuff<i32, 0> = RingBuff::new();
+        buffer.push_back(1);
+        assert_eq!(buffer.push_back_with_evicted(2), Some(2));
+        assert_eq!(buffer.try_push_back(3), Err(3));
+        assert_eq!(buffer.try_push_front(4), Err(4));
+        assert_eq!(buffer.extend_with_evicted(vec![5, 6]).collect::<Vec<i32>>(), vec![5, 6]);
+        assert_eq!(buffer.len_after_push(), 0);
+        assert_eq!(buffer.overwrite_count(), 0);
+
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), 0);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+        assert_eq!(buffer.remove(0), None);
+    }
+
+    #[test]
+    fn zero_capacity_overwrite_policy() {
+        let mut buffer: RingBuff<String, 0> = RingBuff::with_policy(OverflowPolicy::Overwrite);
+        assert_eq!(buffer.push_back_with_evicted("a".to_string()), Some("a".to_string()));
+        buffer.push_back("b".to_string());
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.overwrite_count(), 0);
+    }
+
+    #[test]
+    fn zero_capacity_access() {
+        let mut buffer: RingBuff<i32, 0> = RingBuff::new();
+        assert_eq!(buffer.get(0), None);
+        assert_eq!(buffer.get_mut(0), None);
+        assert!(buffer.get_pair_mut(0).is_none());
+        assert_eq!(buffer.peek_nth(0), None);
+        assert_eq!(buffer.peek_nth_back(0), None);
+        assert_eq!(buffer.iter().next(), None);
+        assert_eq!(buffer.iter().next_back(), None);
+        assert_eq!(buffer.iter_rev().next(), None);
+        assert_eq!(buffer.iter_absolute().next(), None);
+        assert_eq!(buffer.segments().count(), 0);
+        assert_eq!(buffer.as_slices(), (&[][..], &[][..]));
+        assert_eq!(buffer.segment_lengths(), (0, 0));
+        assert!(buffer.windows(1).next().is_none());
+        assert!(buffer.chunks(1).next().is_none());
+        assert_eq!(buffer.min(), None);
+        assert_eq!(buffer.max_copied(), None);
+        assert_eq!(buffer.sum_copied(), 0);
+        assert!(!buffer.contains(&0));
+        assert_eq!(buffer.to_string(), "[]");
+        assert_eq!(format!("{:?}", buffer), "[]");
+        assert_eq!(buffer, RingBuff::default());
+    }
+
+    #[test]
+    fn zero_capacity_mutation() {
+        let mut buffer: RingBuff<i32, 0> = RingBuff::new();
+        buffer.retain(|_| true);
+        buffer.retain_rev(|_| false);
+        buffer.retain_mask(&[]);
+        assert_eq!(buffer.try_retain(|_| Err::<bool, ()>(())), Ok(()));
+        buffer.rotate_left(1);
+        buffer.rotate_right(1);
+        buffer.reverse();
+        buffer.sort();
+        buffer.fill(1);
+        buffer.fill_with(|| 2);
+        buffer.fill_cycling(vec![3]);
+        buffer.truncate(1);
+        buffer.advance(1);
+        assert!(!buffer.try_swap(0, 1));
+        assert!(buffer.drain_while_front(|_| true).is_empty());
+        assert_eq!(buffer.binary_search(&0), Err(0));
+        assert!(buffer.split_off(0).is_empty());
+
+        let mut other: RingBuff<i32, 0> = RingBuff::new();
+        buffer.append(&mut other);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.push_back(1);
+        // SAFETY: the checkpoint was taken on this buffer
+        unsafe { buffer.restore(checkpoint) };
+        buffer.clear();
+        assert!(buffer.is_empty());
+
+        let mapped: RingBuff<i64, 0> = buffer.map(|&x| i64::from(x));
+        assert!(mapped.is_empty());
+        let (data, reader, writer, size) = buffer.into_parts();
+        assert_eq!((data.len(), reader, writer, size), (0, 0, 0, 0));
+        assert!(RingBuff::<i32, 0>::from_parts([], 0, 0, 0).is_ok());
+    }
+
+    static STATIC_BUFFER: RingBuff<i32, 8> = RingBuff::new();
+    const REJECTING_BUFFER: RingBuff<i32, 2> = RingBuff::with_policy(OverflowPolicy::Reject);
+
+    #[test]
+    fn new_in_const_context() {
+        assert!(STATIC_BUFFER.is_empty());
+        assert_eq!(STATIC_BUFFER.capacity(), 8);
+        assert_eq!(STATIC_BUFFER.policy, OverflowPolicy::default());
+
+        let mut buffer = REJECTING_BUFFER;
+        buffer.push_back(1);
+        buffer.push_back(2);
+        buffer.push_back(3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn spsc_push_and_pop() {
+        let (mut producer, mut consumer) = spsc::channel::<i32, 3>();
+        assert_eq!(consumer.pop(), None);
+
+        for i in 0..5 {
+            assert_eq!(producer.push(i), Ok(()));
+        }
+        assert_eq!(producer.len(), 3);
+        assert_eq!(consumer.capacity(), 3);
+        assert_eq!(consumer.pop(), Some(2));
+
+        producer.push(5).unwrap();
+        let result: Vec<i32> = std::iter::from_fn(|| consumer.pop()).collect();
+        assert_eq!(result, vec![3, 4, 5]);
+        assert!(producer.is_empty());
+    }
+
+    #[test]
+    fn spsc_reject_when_full() {
+        let (mut producer, mut consumer) = spsc::channel_with_policy::<i32, 2>(OverflowPolicy::Reject);
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert_eq!(producer.push(3), Err(3));
+
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(3), Ok(()));
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn spsc_zero_capacity() {
+        let (mut producer, mut consumer) = spsc::channel::<i32, 0>();
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(consumer.pop(), None);
+
+        let (mut producer, mut consumer) = spsc::channel_with_policy::<i32, 0>(OverflowPolicy::Reject);
+        assert_eq!(producer.push(1), Err(1));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn spsc_drops_remaining_elements() {
+        let counter = std::sync::Arc::new(());
+        let (mut producer, mut consumer) = spsc::channel::<std::sync::Arc<()>, 4>();
+        for _ in 0..6 {
+            producer.push(std::sync::Arc::clone(&counter)).unwrap();
+        }
+        assert_eq!(std::sync::Arc::strong_count(&counter), 5);
+
+        drop(consumer.pop());
+        drop(producer);
+        assert_eq!(std::sync::Arc::strong_count(&counter), 4);
+        drop(consumer);
+        assert_eq!(std::sync::Arc::strong_count(&counter), 1);
+    }
+
+    /// Streams `COUNT` values from one thread to another,
+    /// returning the values in the order they were received
+    fn spsc_stream(policy: OverflowPolicy) -> Vec<usize> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        const COUNT: usize = if cfg!(miri) { 200 } else { 100_000 };
+
+        let (mut producer, mut consumer) = spsc::channel_with_policy::<usize, 8>(policy);
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let sender = {
+            let finished = Arc::clone(&finished);
+            std::thread::spawn(move || {
+                for i in 0..COUNT {
+                    let mut element = i;
+                    while let Err(rejected) = producer.push(element) {
+                        element = rejected;
+                        std::thread::yield_now();
+                    }
+                }
+                finished.store(true, Ordering::Release);
+            })
+        };
+
+        let mut received = Vec::new();
+        loop {
+            let done = finished.load(Ordering::Acquire);
+            while let Some(element) = consumer.pop() {
+                received.push(element);
+            }
+            if done {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        sender.join().unwrap();
+
+        assert_eq!(received.last(), Some(&(COUNT - 1)));
+        received
+    }
+
+    #[test]
+    fn spsc_stream_overwrite_keeps_order() {
+        let received = spsc_stream(OverflowPolicy::Overwrite);
+        assert!(received.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn spsc_stream_reject_loses_nothing() {
+        let received = spsc_stream(OverflowPolicy::Reject);
+        assert_eq!(received, (0..received.len()).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn as_ptr_reads_contiguous_buffer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+        assert_ne!(buffer.segment_lengths().1, 0);
+
+        buffer.make_contiguous();
+        assert_eq!(buffer.segment_lengths(), (buffer.len(), 0));
+
+        // SAFETY: the buffer is contiguous and left untouched while reading
+        let raw = unsafe { core::slice::from_raw_parts(buffer.as_ptr(), buffer.len()) };
+        assert_eq!(raw.to_vec(), buffer.iter().copied().collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn as_mut_ptr_writes_live_elements() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..6 {
+            buffer.push_back(i);
+        }
+        buffer.make_contiguous();
+
+        let len = buffer.len();
+        let ptr = buffer.as_mut_ptr();
+        for i in 0..len {
+            // SAFETY: the buffer is contiguous, the slots are live
+            unsafe { *ptr.add(i) *= 10 };
+        }
+
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn get_many_mut_disjoint_elements() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..6 {
+            buffer.push_back(i);
+        }
+
+        let [first, last] = buffer.get_many_mut([0, 3]).unwrap();
+        core::mem::swap(first, last);
+        *first += 100;
+
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![105, 3, 4, 2]);
+        assert!(buffer.get_many_mut::<0>([]).is_some());
+    }
+
+    #[test]
+    fn get_many_mut_rejects_invalid_indices() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..3 {
+            buffer.push_back(i);
+        }
+
+        assert!(buffer.get_many_mut([0, 1, 0]).is_none());
+        assert!(buffer.get_many_mut([2, 3]).is_none());
+        assert!(buffer.get_many_mut([usize::MAX]).is_none());
+    }
+
+    #[test]
+    fn nth_newest_on_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..7 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.nth_newest(0), buffer.iter().next_back());
+        assert_eq!(buffer.nth_newest(0), Some(&6));
+        assert_eq!(buffer.nth_newest(1), Some(&5));
+        assert_eq!(buffer.nth_newest(3), Some(&3));
+        assert_eq!(buffer.nth_newest(4), None);
+        assert_eq!(buffer.nth_newest(usize::MAX), None);
+    }
+
+    #[test]
+    fn dedup_wrapped_buffer() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::new();
+        for i in [9, 9, 9, 1, 1, 2, 3, 3, 3, 1, 1, 4] {
+            buffer.push_back(i);
+        }
+        assert_ne!(buffer.segment_lengths().1, 0);
+
+        buffer.dedup();
+
+        assert_eq!(buffer.len(), 5);
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3, 1, 4]);
+        assert_eq!(buffer.validate(), Ok(()));
+
+        buffer.push_back(4);
+        buffer.dedup();
+        assert_eq!(buffer.len(), 5);
+    }
+
+    #[test]
+    fn dedup_by_key_and_by() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in [10, 11, 20, 25, 31, 40, 41, 42] {
+            buffer.push_back(i);
+        }
+
+        buffer.dedup_by_key(|x| *x / 10);
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![20, 31, 40]);
+
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in [1, 2, 3, 6] {
+            buffer.push_back(i);
+        }
+        buffer.dedup_by(|current, previous| {
+            let duplicate = *current == *previous + 1;
+            if duplicate {
+                *previous = *current;
+            }
+            duplicate
+        });
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), vec![3, 6]);
+    }
+
+    #[test]
+    fn count_matching_even_numbers() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.count_matching(|x| x % 2 == 0), 2);
+        assert_eq!(buffer.count_matching(|_| true), buffer.len());
+        assert_eq!(buffer.count_matching(|&x| x > 10), 0);
+    }
+
+    #[test]
+    fn fold_and_reduce_wrapped_buffer() {
+        let mut buffer: RingBuff<i64, 4> = RingBuff::new();
+        for i in 1..7 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.fold(0, |acc, x| acc + x), 18);
+        let running: Vec<i64> = buffer.fold(Vec::new(), |mut products, x| {
+            products.push(products.last().unwrap_or(&1) * x);
+            products
+        });
+        assert_eq!(running, vec![3, 12, 60, 360]);
+
+        assert_eq!(buffer.reduce(|acc, x| acc * x), Some(360));
+        assert_eq!(RingBuff::<i64, 4>::new().reduce(|acc, x| acc * x), None);
+    }
+
+    /// Builds a buffer holding `len` elements with its oldest one at `offset`
+    fn buffer_at<const CAP: usize>(policy: OverflowPolicy, offset: usize, len: usize) -> RingBuff<u32, CAP> {
+        let mut buffer = RingBuff::with_policy(policy);
+        for i in 0..offset {
+            buffer.push_back(i as u32);
+        }
+        for _ in 0..offset {
+            buffer.pop();
+        }
+        for i in 0..len {
+            buffer.push_back(100 + i as u32);
+        }
+        buffer
+    }
+
+    #[test]
+    fn copy_from_slice_matches_push_back() {
+        let src: Vec<u32> = (1000..1020).collect();
+
+        for policy in [OverflowPolicy::Overwrite, OverflowPolicy::Reject] {
+            for offset in [0, 3, 5] {
+                for len in [0, 2, 6] {
+                    for count in [0, 1, 3, 6, 9, 20] {
+                        let mut copied: RingBuff<u32, 6> = buffer_at(policy, offset, len);
+                        let mut pushed: RingBuff<u32, 6> = buffer_at(policy, offset, len);
+                        copied.copy_from_slice(&src[..count]);
+                        for &element in &src[..count] {
+                            pushed.push_back(element);
+                        }
+
+                        assert_eq!(copied, pushed, "{:?} {} {} {}", policy, offset, len, count);
+                        assert_eq!((copied.reader, copied.writer, copied.size), (pushed.reader, pushed.writer, pushed.size));
+                        assert_eq!(copied.pushes, pushed.pushes);
+                        assert_eq!(copied.overwrite_count(), pushed.overwrite_count());
+                        assert_eq!(copied.front_id, pushed.front_id);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partition_by_parity() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for i in [1, 2, 3] {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+        for i in [4, 5, 6, 7] {
+            buffer.push_back(i);
+        }
+
+        let (even, odd) = buffer.partition(|x| x % 2 == 0);
+
+        assert_eq!(even.iter().copied().collect::<Vec<i32>>(), vec![2, 4, 6]);
+        assert_eq!(odd.iter().copied().collect::<Vec<i32>>(), vec![3, 5, 7]);
+        assert_eq!(even.policy, OverflowPolicy::Reject);
+        assert_eq!(buffer.len(), 6);
+    }
+
+    #[test]
+    fn clone_keeps_elements_and_counters() {
+        let mut buffer: RingBuff<String, 3> = RingBuff::new();
+        for i in 0..5 {
+            buffer.push_back(i.to_string());
+        }
+
+        let clone = buffer.clone();
+        assert_eq!(clone, buffer);
+        assert_eq!(clone.overwrite_count(), 2);
+        assert_eq!(clone.iter_stable_enumerate().next(), Some((2, &"2".to_string())));
+    }
+
+    #[test]
+    fn clone_from_into_populated_buffer() {
+        let mut source: RingBuff<String, 4> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for word in ["a", "b", "c"] {
+            source.push_back(word.to_string());
+        }
+
+        let mut destination: RingBuff<String, 4> = RingBuff::new();
+        for i in 0..6 {
+            destination.push_back(format!("{:>32}", i));
+        }
+        let reused = destination.get(0).unwrap().as_ptr();
+
+        destination.clone_from(&source);
+        assert_eq!(destination, source);
+        assert_eq!(destination.policy, OverflowPolicy::Reject);
+        assert_eq!(destination.overwrite_count(), 0);
+        // The allocation of the first string was reused
+        assert_eq!(destination.get(0).unwrap().as_ptr(), reused);
+
+        let mut shorter: RingBuff<String, 4> = RingBuff::new();
+        shorter.push_back("x".to_string());
+        shorter.clone_from(&source);
+        assert_eq!(shorter, source);
+    }
+
+    #[test]
+    fn dyn_clone_from_reuses_storage() {
+        let mut source: DynRingBuff<i32> = DynRingBuff::with_capacity(3);
+        for i in 0..5 {
+            source.push_back(i);
+        }
+
+        let mut destination: DynRingBuff<i32> = DynRingBuff::with_capacity(3);
+        destination.push_back(9);
+        destination.clone_from(&source);
+
+        assert_eq!(destination.iter().collect::<Vec<&i32>>(), source.iter().collect::<Vec<&i32>>());
+        assert_eq!(destination.capacity(), 3);
+        assert_eq!(source.clone().iter().collect::<Vec<&i32>>(), vec![&2, &3, &4]);
+    }
+
+    #[test]
+    fn compare_with_arrays_slices_and_vecs() {
+        let mut buffer: RingBuff<i32, 3> = RingBuff::new();
+        for i in 100..104 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer, [101, 102, 103]);
+        assert_eq!([101, 102, 103], buffer);
+        assert_eq!(buffer, vec![101, 102, 103]);
+        assert_eq!(vec![101, 102, 103], buffer);
+        assert_eq!(buffer, [101, 102, 103][..]);
+        assert_eq!([101, 102, 103][..], buffer);
+
+        assert_ne!(buffer, [101, 102]);
+        assert_ne!(buffer, vec![102, 101, 103]);
+        assert_ne!(Vec::<i32>::new(), buffer);
+        assert_eq!(RingBuff::<i32, 3>::new(), [0; 0]);
+    }
+
+    #[test]
+    fn checked_push_reports_capacity_error() {
+        let mut buffer: RingBuff<String, 2> = RingBuff::new();
+        assert!(buffer.checked_push("a".to_string()).is_ok());
+        assert!(buffer.checked_push("b".to_string()).is_ok());
+
+        let result: Result<(), CapacityError<String>> = buffer.checked_push("c".to_string());
+        match result {
+            Ok(()) => panic!("The buffer is full."),
+            Err(error) => {
+                assert_eq!(error.element(), "c");
+                assert_eq!(error.to_string(), "the buffer is full");
+                assert_eq!(format!("{:?}", error), "CapacityError { .. }");
+                assert_eq!(error.into_inner(), "c");
+            }
+        }
+        assert_eq!(buffer, ["a", "b"]);
+        assert_eq!(buffer.overwrite_count(), 0);
+
+        let error: Box<dyn std::error::Error> = Box::new(buffer.checked_push("d".to_string()).unwrap_err());
+        assert_eq!(error.to_string(), "the buffer is full");
+    }
+
+    #[test]
+    fn normalize_moves_reader_to_start() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+        assert_eq!(buffer.reader, 4);
+
+        buffer.normalize();
+
+        assert_eq!(buffer, [4, 5, 6, 7]);
+        assert_eq!((buffer.reader, buffer.writer), (0, 4));
+        assert_eq!(buffer.validate(), Ok(()));
+
+        let mut empty: RingBuff<i32, 3> = RingBuff::new();
+        empty.push_back(1);
+        empty.pop();
+        empty.normalize();
+        assert_eq!((empty.reader, empty.writer), (0, 0));
+    }
+
+    #[test]
+    fn iter_nth_matches_get() {
+        let mut buffer: RingBuff<i32, 7> = RingBuff::new();
+        for i in 0..11 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+
+        for k in 0..8 {
+            assert_eq!(buffer.iter().nth(k), buffer.get(k));
+        }
+
+        let mut iter = buffer.iter();
+        assert_eq!(iter.nth(1), Some(&6));
+        assert_eq!(iter.nth(2), Some(&9));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&10));
+        assert_eq!(iter.nth(3), None);
+
+        let mut iter = buffer.iter();
+        assert_eq!(iter.nth(6), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(buffer.iter().skip(4).copied().collect::<Vec<i32>>(), vec![9, 10]);
+    }
+
+    #[test]
+    fn extract_if_odd_numbers() {
+        let mut buffer: RingBuff<i32, 6> = RingBuff::new();
+        for i in 0..10 {
+            buffer.push_back(i);
+        }
+
+        let odd: Vec<i32> = buffer.extract_if(|x| *x % 2 != 0).collect();
+
+        assert_eq!(odd, vec![5, 7, 9]);
+        assert_eq!(buffer, [4, 6, 8]);
+        assert_eq!(buffer.validate(), Ok(()));
+
+        let front: Vec<i32> = buffer.extract_if(|x| *x < 6).collect();
+        assert_eq!(front, vec![4]);
+        assert_eq!(buffer.iter_stable_enumerate().next(), Some((5, &6)));
+    }
+
+    #[test]
+    fn extract_if_dropped_early_keeps_unchecked() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..7 {
+            buffer.push_back(i);
+        }
+
+        let mut extract = buffer.extract_if(|x| {
+            *x *= 10;
+            true
+        });
+        assert_eq!(extract.next(), Some(20));
+        assert_eq!(extract.next(), Some(30));
+        drop(extract);
+
+        assert_eq!(buffer, [4, 5, 6]);
+        assert_eq!(buffer.validate(), Ok(()));
+    }
+
+    #[test]
+    fn extract_if_leaked_leaves_buffer_empty() {
+        let drops = Cell::new(0);
+        let mut buffer: RingBuff<DropCounter, 3> = RingBuff::new();
+        for _ in 0..3 {
+            buffer.push_back(DropCounter(&drops));
+        }
+
+        assert!(buffer.extract_if(|_| false).next().is_none());
+        assert_eq!(buffer.len(), 3);
+        core::mem::forget(buffer.extract_if(|_| true));
+
+        assert!(buffer.is_empty());
+        drop(buffer);
+        assert_eq!(drops.get(), 0);
+    }
+
+    #[test]
+    fn starts_with_and_ends_with_on_wrapped_bytes() {
+        let mut buffer: RingBuff<u8, 6> = RingBuff::new();
+        for &byte in b"xxSTART-END" {
+            buffer.push_back(byte);
+        }
+        assert_eq!(buffer, *b"RT-END");
+
+        assert!(buffer.starts_with(b"RT"));
+        assert!(buffer.starts_with(b""));
+        assert!(buffer.starts_with(b"RT-END"));
+        assert!(!buffer.starts_with(b"ST"));
+        assert!(!buffer.starts_with(b"RT-END!"));
+
+        assert!(buffer.ends_with(b"END"));
+        assert!(buffer.ends_with(b""));
+        assert!(!buffer.ends_with(b"EN"));
+        assert!(!buffer.ends_with(b"?RT-END"));
+    }
+
+    #[test]
+    fn cursor_moves_both_ways() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..7 {
+            buffer.push_back(i);
+        }
+
+        let mut cursor = buffer.cursor();
+        assert_eq!((cursor.position(), cursor.current()), (0, Some(&3)));
+        assert!(!cursor.move_prev());
+
+        let mut seen = vec![*cursor.current().unwrap()];
+        while cursor.move_next() {
+            seen.push(*cursor.current().unwrap());
+        }
+        assert_eq!(seen, vec![3, 4, 5, 6]);
+        assert_eq!(cursor.position(), 3);
+
+        assert!(cursor.move_prev());
+        assert_eq!(cursor.current(), Some(&5));
+        let saved = cursor.clone();
+        cursor.rewind();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(saved.current(), Some(&5));
+
+        assert!(cursor.seek(2));
+        assert_eq!(cursor.current(), Some(&5));
+        assert!(!cursor.seek(4));
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    fn cursor_on_empty_buffer() {
+        let buffer: RingBuff<i32, 4> = RingBuff::new();
+        let mut cursor = buffer.cursor();
+
+        assert_eq!(cursor.current(), None);
+        assert!(!cursor.move_next());
+        assert!(!cursor.move_prev());
+        assert!(!cursor.seek(0));
+    }
+
+    #[test]
+    fn range_across_wrap_boundary() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in 0..8 {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.segment_lengths(), (2, 3));
+
+        assert_eq!(buffer.range(1..4).copied().collect::<Vec<i32>>(), vec![4, 5, 6]);
+        assert_eq!(buffer.range(0..5).rev().copied().collect::<Vec<i32>>(), vec![7, 6, 5, 4, 3]);
+        assert_eq!(buffer.range(2..2).count(), 0);
+        assert_eq!(buffer.range(3..100).copied().collect::<Vec<i32>>(), vec![6, 7]);
+        assert_eq!(buffer.range(7..9).count(), 0);
+        assert_eq!(buffer.range(1..4).len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Range start is greater than range end.")]
+    fn range_inverted_panics() {
+        let buffer: RingBuff<i32, 5> = RingBuff::new();
+        let (start, end) = (3, 1);
+        buffer.range(start..end);
+    }
+
+    #[test]
+    fn is_contiguous_states() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        assert!(buffer.is_contiguous());
+
+        for i in 0..3 {
+            buffer.push_back(i);
+        }
+        buffer.pop();
+        assert!(buffer.is_contiguous());
+
+        buffer.push_back(3);
+        assert!(buffer.is_contiguous());
+        buffer.push_back(4);
+        assert!(!buffer.is_contiguous());
+        assert_ne!(buffer.segment_lengths().1, 0);
+
+        buffer.make_contiguous();
+        assert!(buffer.is_contiguous());
+
+        buffer.clear();
+        assert!(buffer.is_contiguous());
+        assert!(RingBuff::<i32, 0>::new().is_contiguous());
+    }
+
+    #[test]
+    fn load_factor_and_remaining_capacity() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        assert_eq!((buffer.load_factor(), buffer.remaining_capacity()), (0.0, 4));
+
+        buffer.push_back(1);
+        buffer.push_back(2);
+        assert_eq!((buffer.load_factor(), buffer.remaining_capacity()), (0.5, 2));
+
+        for i in 3..7 {
+            buffer.push_back(i);
+        }
+        assert_eq!((buffer.load_factor(), buffer.remaining_capacity()), (1.0, 0));
+
+        let empty: RingBuff<i32, 0> = RingBuff::new();
+        assert_eq!((empty.load_factor(), empty.remaining_capacity()), (0.0, 0));
+    }
+
+    #[test]
+    fn position_max_and_min() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        for i in [50, 7, 3, 9, -4, 8, 1] {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer, [3, 9, -4, 8, 1]);
+
+        assert_eq!(buffer.position_max(), Some(1));
+        assert_eq!(buffer.position_min(), Some(2));
+        assert_eq!(buffer.remove(buffer.position_max().unwrap()), Some(9));
+
+        let mut ties: RingBuff<i32, 5> = RingBuff::new();
+        for i in [0, 0, 2, 5, 2, 5, 2] {
+            ties.push_back(i);
+        }
+        assert_eq!(ties, [2, 5, 2, 5, 2]);
+        assert_eq!(ties.position_max(), Some(1));
+        assert_eq!(ties.position_min(), Some(0));
+
+        assert_eq!(RingBuff::<i32, 5>::new().position_max(), None);
+        assert_eq!(RingBuff::<i32, 5>::new().position_min(), None);
+    }
+
+    #[test]
+    fn iter_rotated_leaves_buffer_unchanged() {
+        let mut buffer: RingBuff<char, 4> = RingBuff::new();
+        for c in ['x', 'y', 'a', 'b', 'c', 'd'] {
+            buffer.push_back(c);
+        }
+
+        assert_eq!(buffer.iter_rotated(2).copied().collect::<String>(), "cdab");
+        assert_eq!(buffer.iter().copied().collect::<String>(), "abcd");
+        assert_eq!(buffer.reader, 2);
+
+        assert_eq!(buffer.iter_rotated(0).copied().collect::<String>(), "abcd");
+        assert_eq!(buffer.iter_rotated(7).copied().collect::<String>(), "dabc");
+        assert_eq!(RingBuff::<char, 4>::new().iter_rotated(3).count(), 0);
+    }
+
+    #[test]
+    fn debug_layout_marks_reader_and_writer() {
+        let mut buffer: RingBuff<i32, 5> = RingBuff::new();
+        buffer.push_back(99);
+        buffer.pop();
+        buffer.push_back(100);
+        buffer.push_back(101);
+        assert_eq!(buffer.debug_layout(), "[_, R:100, 101, W:_, _]");
+
+        for i in 102..106 {
+            buffer.push_back(i);
+        }
+        assert_eq!(buffer.debug_layout(), "[104, 105, RW:101, 102, 103]");
+
+        buffer.clear();
+        assert_eq!(buffer.debug_layout(), "[RW:_, _, _, _, _]");
+        assert_eq!(RingBuff::<i32, 0>::new().debug_layout(), "[]");
+    }
+
+    #[test]
+    fn from_vec_keeps_newest_elements() {
+        let shorter: RingBuff<i32, 4> = RingBuff::from(vec![1, 2]);
+        assert_eq!(shorter, [1, 2]);
+
+        let equal: RingBuff<i32, 4> = vec![1, 2, 3, 4].into();
+        assert_eq!(equal, [1, 2, 3, 4]);
+
+        let longer: RingBuff<String, 4> = RingBuff::from((0..7).map(|i| i.to_string()).collect::<Vec<String>>());
+        assert_eq!(longer, ["3", "4", "5", "6"]);
+        assert_eq!(longer.overwrite_count(), 0);
+
+        let empty: RingBuff<i32, 0> = RingBuff::from(vec![1]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_iter_sum_matches_sequential_sum() {
+        use rayon::prelude::*;
+
+        let mut buffer: Box<RingBuff<u64, 50_000>> = Box::default();
+        let elements: Vec<u64> = (0..80_000).collect();
+        buffer.copy_from_slice(&elements);
+        assert_ne!(buffer.segment_lengths().1, 0);
+
+        assert_eq!(buffer.par_iter().sum::<u64>(), buffer.iter().sum::<u64>());
+        assert_eq!(buffer.par_iter().len(), buffer.len());
+
+        let doubled: Vec<u64> = buffer.par_iter().map(|x| x * 2).collect();
+        assert_eq!(doubled, buffer.iter().map(|x| x * 2).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn get_wrapping_mut_updates_cyclic_table() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::new();
+        for i in 0..6 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.get_wrapping(5), Some(&3));
+        *buffer.get_wrapping_mut(5).unwrap() += 100;
+        *buffer.get_wrapping_mut(8).unwrap() *= -1;
+
+        assert_eq!(buffer, [-2, 103, 4, 5]);
+        assert_eq!(buffer.get_wrapping(usize::MAX), Some(&5));
+
+        let mut empty: RingBuff<i32, 4> = RingBuff::new();
+        assert_eq!(empty.get_wrapping(3), None);
+        assert_eq!(empty.get_wrapping_mut(0), None);
+    }
+
+    #[test]
+    fn insert_sorted_keeps_order() {
+        let mut buffer: RingBuff<i32, 8> = RingBuff::new();
+        for i in 0..6 {
+            buffer.push_back(i);
+        }
+        for _ in 0..6 {
+            buffer.pop();
+        }
+
+        for (element, index) in [(50, 0), (10, 0), (30, 1), (40, 2), (60, 4), (20, 1), (35, 3)] {
+            assert_eq!(buffer.insert_sorted(element), index);
+            assert_eq!(buffer.validate(), Ok(()));
+        }
+
+        assert_eq!(buffer, [10, 20, 30, 35, 40, 50, 60]);
+        assert_ne!(buffer.segment_lengths().1, 0);
+    }
+
+    #[test]
+    fn insert_sorted_full_buffer_drops_smallest() {
+        let mut buffer: RingBuff<i32, 4> = RingBuff::with_policy(OverflowPolicy::Reject);
+        for element in [40, 10, 30, 20] {
+            buffer.insert_sorted(element);
+        }
+        assert_eq!(buffer, [10, 20, 30, 40]);
+
+        assert_eq!(buffer.insert_sorted(25), 1);
+        assert_eq!(buffer, [20, 25, 30, 40]);
+        assert_eq!(buffer.insert_sorted(5), 0);
+        assert_eq!(buffer, [5, 25, 30, 40]);
+        assert_eq!(buffer.overwrite_count(), 2);
+        assert_eq!(buffer.validate(), Ok(()));
+
+        let mut empty: RingBuff<i32, 0> = RingBuff::new();
+        assert_eq!(empty.insert_sorted(1), 0);
+        assert!(empty.is_empty());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+//! Index arithmetic shared by the buffers
+//!
+//! `RingBuff`, `DynRingBuff` and the `spsc` channel store their
+//! elements differently but move through their slots the same way,
+//! only the capacity comes from a const generic or from the data.
+
+/// Returns the index of the next slot, wrapping to 0
+/// after the last one.
+///
+/// # Arguments
+///
+/// * `index` - The original index
+/// * `capacity` - Number of slots
+///
+pub(crate) const fn next(index: usize, capacity: usize) -> usize {
+    if index + 1 >= capacity {
+        0
+    } else {
+        index + 1
+    }
+}
+
+/// Returns the index of the previous slot, wrapping to
+/// the last one before 0.
+///
+/// # Arguments
+///
+/// * `index` - The original index
+/// * `capacity` - Number of slots
+///
+pub(crate) const fn previous(index: usize, capacity: usize) -> usize {
+    if index == 0 {
+        capacity.saturating_sub(1)
+    } else {
+        index - 1
+    }
+}
+
+/// Returns the index of the slot `offset` slots after `start`.
+///
+/// # Arguments
+///
+/// * `start` - The original index, lower than `capacity`
+/// * `offset` - Number of slots to move by, at most `capacity`
+/// * `capacity` - Number of slots
+///
+pub(crate) const fn add(start: usize, offset: usize, capacity: usize) -> usize {
+    // `start < capacity` and `offset <= capacity`, one subtraction is enough
+    let index = start + offset;
+    if index >= capacity {
+        index - capacity
+    } else {
+        index
+    }
+}